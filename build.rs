@@ -0,0 +1,22 @@
+use std::fs;
+
+fn main() {
+    println!("cargo:rerun-if-changed=Cargo.lock");
+
+    let lockfile = fs::read_to_string("Cargo.lock").unwrap_or_default();
+    let mut lines = lockfile.lines();
+    let mut version = "unknown".to_string();
+
+    while let Some(line) = lines.next() {
+        if line == "name = \"solana-sdk\"" {
+            if let Some(next) = lines.next() {
+                if let Some(value) = next.strip_prefix("version = \"") {
+                    version = value.trim_end_matches('"').to_string();
+                }
+            }
+            break;
+        }
+    }
+
+    println!("cargo:rustc-env=SOLANA_SDK_VERSION={}", version);
+}
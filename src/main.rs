@@ -1,11 +1,173 @@
-use solana_sdk::signer::keypair::Keypair;
-use solana_sdk::signer::Signer;
-use axum::{Router, Json, routing::{get, post}, http::StatusCode, response::IntoResponse};
-use serde::{Serialize, Deserialize};
+use anyhow::Context;
+use axum::extract::ws::{Message as WsMessage, WebSocket, WebSocketUpgrade};
+use axum::{
+    async_trait,
+    extract::{
+        rejection::JsonRejection, DefaultBodyLimit, FromRequest, MatchedPath, Path, Query, Request,
+        State,
+    },
+    http::{header, HeaderName, HeaderValue, Method, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use axum::{error_handling::HandleErrorLayer, BoxError};
+use axum_server::tls_rustls::RustlsConfig;
 use base64::Engine;
-use std::net::SocketAddr;
-use solana_sdk::pubkey::Pubkey;
+use bip39::{Language, Mnemonic, Seed};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use solana_client::client_error::ClientError;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::address_lookup_table_account::AddressLookupTableAccount;
+use solana_sdk::derivation_path::DerivationPath;
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::message::{v0, Message, VersionedMessage};
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::offchain_message::OffchainMessage;
+use solana_sdk::packet::PACKET_DATA_SIZE;
+use solana_sdk::program_pack::Pack;
+use solana_sdk::pubkey::{Pubkey, MAX_SEEDS, MAX_SEED_LEN};
+use solana_sdk::rent::Rent;
 use solana_sdk::signature::Signature;
+use solana_sdk::signer::keypair::{
+    keypair_from_seed, keypair_from_seed_and_derivation_path, Keypair,
+};
+use solana_sdk::signer::Signer;
+use solana_sdk::system_instruction::MAX_PERMITTED_DATA_LENGTH;
+use solana_sdk::system_program;
+use solana_sdk::transaction::{Transaction, VersionedTransaction};
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{Mutex, OwnedSemaphorePermit, Semaphore};
+use tower::limit::GlobalConcurrencyLimitLayer;
+use tower::ServiceBuilder;
+use tower_http::catch_panic::CatchPanicLayer;
+use tower_http::compression::CompressionLayer;
+use tower_http::cors::{AllowHeaders, AllowOrigin, CorsLayer};
+use tower_http::trace::{DefaultOnResponse, TraceLayer};
+use tower_http::LatencyUnit;
+use tracing::Level;
+use tracing_subscriber::EnvFilter;
+
+// Everything read from the command line and environment at startup, resolved
+// once in `main` so a bad value fails before the server binds.
+struct Config {
+    cluster: Cluster,
+    bind_addr: SocketAddr,
+    tls_paths: Option<(String, String)>,
+    api_keys: Vec<String>,
+    cors: CorsLayer,
+    max_body_bytes: usize,
+    max_message_bytes: usize,
+    request_timeout: Duration,
+    max_concurrent_requests: usize,
+}
+
+#[derive(Clone)]
+struct AppState {
+    started_at: Instant,
+    config: Arc<Config>,
+    metrics: Arc<Metrics>,
+    rpc_client: Arc<RpcClient>,
+    blockhash_cache: Arc<Mutex<Option<CachedBlockhash>>>,
+    vanity_searches: Arc<Semaphore>,
+}
+
+#[derive(Clone, Copy)]
+struct CachedBlockhash {
+    blockhash: Hash,
+    last_valid_block_height: u64,
+    fetched_at: Instant,
+}
+
+const LATENCY_BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+#[derive(Default)]
+struct RouteMetrics {
+    count: u64,
+    latency_sum: f64,
+    latency_buckets: [u64; LATENCY_BUCKETS.len()],
+}
+
+#[derive(Default)]
+struct Metrics {
+    routes: std::sync::Mutex<BTreeMap<(String, &'static str), RouteMetrics>>,
+}
+
+impl Metrics {
+    fn record(&self, route: String, status: StatusCode, latency: Duration) {
+        let status_class = match status.as_u16() {
+            100..=199 => "1xx",
+            200..=299 => "2xx",
+            300..=399 => "3xx",
+            400..=499 => "4xx",
+            _ => "5xx",
+        };
+        let seconds = latency.as_secs_f64();
+
+        let mut routes = self
+            .routes
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let entry = routes.entry((route, status_class)).or_default();
+        entry.count += 1;
+        entry.latency_sum += seconds;
+        for (bucket, bound) in entry.latency_buckets.iter_mut().zip(LATENCY_BUCKETS) {
+            if seconds <= bound {
+                *bucket += 1;
+            }
+        }
+    }
+
+    fn render(&self) -> String {
+        let routes = self
+            .routes
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let mut output = String::new();
+
+        output.push_str("# HELP http_requests_total Total number of HTTP requests.\n");
+        output.push_str("# TYPE http_requests_total counter\n");
+        for ((route, status), metrics) in routes.iter() {
+            output.push_str(&format!(
+                "http_requests_total{{route=\"{}\",status=\"{}\"}} {}\n",
+                route, status, metrics.count
+            ));
+        }
+
+        output.push_str("# HELP http_request_duration_seconds HTTP request latency in seconds.\n");
+        output.push_str("# TYPE http_request_duration_seconds histogram\n");
+        for ((route, status), metrics) in routes.iter() {
+            for (bucket, bound) in metrics.latency_buckets.iter().zip(LATENCY_BUCKETS) {
+                output.push_str(&format!(
+                    "http_request_duration_seconds_bucket{{route=\"{}\",status=\"{}\",le=\"{}\"}} {}\n",
+                    route, status, bound, bucket
+                ));
+            }
+            output.push_str(&format!(
+                "http_request_duration_seconds_bucket{{route=\"{}\",status=\"{}\",le=\"+Inf\"}} {}\n",
+                route, status, metrics.count
+            ));
+            output.push_str(&format!(
+                "http_request_duration_seconds_sum{{route=\"{}\",status=\"{}\"}} {}\n",
+                route, status, metrics.latency_sum
+            ));
+            output.push_str(&format!(
+                "http_request_duration_seconds_count{{route=\"{}\",status=\"{}\"}} {}\n",
+                route, status, metrics.count
+            ));
+        }
+
+        output
+    }
+}
 
 #[derive(Serialize)]
 struct ApiResponse<T> {
@@ -13,16 +175,49 @@ struct ApiResponse<T> {
     data: T,
 }
 
-#[derive(Serialize)]
+#[derive(Debug, Serialize, Clone, Copy)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+enum ErrorCode {
+    InvalidRequest,
+    MissingFields,
+    InvalidPubkey,
+    InvalidSecretKey,
+    InvalidSignature,
+    InvalidAmount,
+    AmountZero,
+    InvalidTransaction,
+    InvalidInput,
+    LimitExceeded,
+    Unauthorized,
+    NotFound,
+    Timeout,
+    Overloaded,
+    RpcError,
+    InternalError,
+}
+
+#[derive(Debug, Serialize)]
 struct ErrorResponse {
     success: bool,
+    code: ErrorCode,
     error: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    request_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    details: Option<String>,
 }
 
 #[derive(Serialize)]
 struct KeypairData {
     pubkey: String,
-    secret: String,
+    secret: SecretData,
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+enum SecretData {
+    Base58(String),
+    Bytes(Vec<u8>),
 }
 
 #[derive(Serialize)]
@@ -31,9 +226,34 @@ struct MessageData {
 }
 
 #[derive(Serialize)]
+struct VersionData {
+    name: String,
+    version: String,
+    #[serde(rename = "solanaSdk")]
+    solana_sdk: String,
+}
+
+#[derive(Serialize)]
+struct RpcHealthData {
+    status: String,
+    cluster: String,
+    latency_ms: u64,
+}
+
+#[derive(Serialize)]
+struct HealthData {
+    status: String,
+    version: String,
+    cluster: String,
+    uptime_seconds: u64,
+}
+
+#[derive(Serialize, Deserialize)]
 struct AccountMeta {
     pubkey: String,
+    #[serde(alias = "isSigner")]
     is_signer: bool,
+    #[serde(alias = "isWritable")]
     is_writable: bool,
 }
 
@@ -45,24 +265,193 @@ struct InstructionData {
 }
 
 #[derive(Serialize)]
-struct SolTransferData {
-    program_id: String,
-    accounts: Vec<String>,
-    instruction_data: String,
+struct AddressData {
+    address: String,
 }
 
 #[derive(Serialize)]
-struct TokenTransferData {
-    program_id: String,
-    accounts: Vec<TokenAccountMeta>,
-    instruction_data: String,
+struct LookupTableData {
+    address: String,
+    recent_slot: u64,
+    instruction: InstructionData,
 }
 
 #[derive(Serialize)]
-struct TokenAccountMeta {
+struct FingerprintData {
     pubkey: String,
-    #[serde(rename = "isSigner")]
-    is_signer: bool,
+    fingerprint: String,
+}
+
+#[derive(Serialize)]
+struct BulkTransferData {
+    instructions: Vec<InstructionData>,
+    total_lamports: u64,
+}
+
+#[derive(Serialize)]
+struct SeededAccountData {
+    address: String,
+    instruction: InstructionData,
+}
+
+#[derive(Serialize)]
+struct PubkeyValidationData {
+    valid: bool,
+    #[serde(rename = "onCurve")]
+    on_curve: bool,
+}
+
+#[derive(Serialize)]
+struct SolanaPayUrlData {
+    url: String,
+}
+
+#[derive(Serialize)]
+struct SolData {
+    sol: String,
+}
+
+#[derive(Serialize)]
+struct LamportsData {
+    lamports: u64,
+}
+
+#[derive(Serialize)]
+struct TokenAmountData {
+    amount: u64,
+}
+
+#[derive(Serialize)]
+struct BalanceData {
+    lamports: u64,
+    sol: String,
+}
+
+#[derive(Serialize)]
+struct TransactionSignatureData {
+    signature: String,
+}
+
+#[derive(Serialize)]
+struct TokenAccountStateData {
+    mint: String,
+    owner: String,
+    amount: u64,
+    delegate: Option<String>,
+    state: &'static str,
+    is_native: Option<u64>,
+    delegated_amount: u64,
+    close_authority: Option<String>,
+}
+
+#[derive(Serialize)]
+struct MintStateData {
+    mint_authority: Option<String>,
+    supply: u64,
+    decimals: u8,
+    is_initialized: bool,
+    freeze_authority: Option<String>,
+}
+
+#[derive(Serialize)]
+struct PriorityFeeData {
+    samples: usize,
+    min: Option<u64>,
+    median: Option<u64>,
+    max: Option<u64>,
+}
+
+#[derive(Serialize)]
+struct SignatureStatusData {
+    found: bool,
+    slot: Option<u64>,
+    confirmations: Option<usize>,
+    confirmation_status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+#[derive(Serialize)]
+struct SimulationData {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    logs: Vec<String>,
+    units_consumed: Option<u64>,
+}
+
+#[derive(Serialize)]
+struct TransactionSizeData {
+    size: usize,
+    message_size: usize,
+    signatures: usize,
+    max_size: usize,
+    exceeds_limit: bool,
+}
+
+#[derive(Serialize)]
+struct DecodedTransactionData {
+    version: String,
+    fee_payer: String,
+    recent_blockhash: String,
+    signatures: Vec<String>,
+    account_keys: Vec<String>,
+    lookup_tables: Vec<String>,
+    instruction_count: usize,
+    instructions: Vec<DecodedTransactionInstruction>,
+}
+
+#[derive(Serialize)]
+struct DecodedTransactionInstruction {
+    program_id: String,
+    accounts: Vec<u8>,
+    data_length: usize,
+}
+
+#[derive(Serialize)]
+struct BuiltTransactionData {
+    transaction: String,
+    message: String,
+    blockhash: String,
+    version: String,
+}
+
+#[derive(Serialize)]
+struct BlockhashData {
+    blockhash: String,
+    last_valid_block_height: u64,
+}
+
+#[derive(Serialize)]
+struct PdaData {
+    address: String,
+    bump: u8,
+}
+
+#[derive(Serialize)]
+struct DecodedInstructionData {
+    program: &'static str,
+    opcode: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    instruction: Option<String>,
+    data_length: usize,
+}
+
+#[derive(Serialize)]
+struct Base58Data {
+    value: String,
+}
+
+#[derive(Serialize)]
+struct BytesData {
+    bytes: String,
+    length: usize,
+}
+
+#[derive(Serialize)]
+struct HashData {
+    algorithm: String,
+    hex: String,
+    base64: String,
 }
 
 #[derive(Serialize)]
@@ -70,6 +459,10 @@ struct SignatureData {
     signature: String,
     public_key: String,
     message: String,
+    #[serde(rename = "messageBytesBase64", skip_serializing_if = "Option::is_none")]
+    message_bytes_base64: Option<String>,
+    #[serde(rename = "messageLength", skip_serializing_if = "Option::is_none")]
+    message_length: Option<usize>,
 }
 
 #[derive(Serialize)]
@@ -79,12 +472,71 @@ struct VerifyData {
     pubkey: String,
 }
 
+#[derive(Serialize)]
+struct BatchVerifyData {
+    index: usize,
+    valid: bool,
+    message: String,
+    pubkey: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct KeypairQuery {
+    format: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct BatchKeypairRequest {
+    count: u16,
+}
+
+#[derive(Deserialize)]
+struct VanityKeypairRequest {
+    prefix: String,
+    #[serde(rename = "caseSensitive", default = "default_case_sensitive")]
+    case_sensitive: bool,
+    #[serde(rename = "timeoutMs")]
+    timeout_ms: Option<u64>,
+}
+
+fn default_case_sensitive() -> bool {
+    true
+}
+
+#[derive(Deserialize)]
+struct MnemonicKeypairRequest {
+    mnemonic: String,
+    #[serde(default)]
+    passphrase: String,
+    #[serde(rename = "derivationPath")]
+    derivation_path: Option<String>,
+    account: Option<u32>,
+}
+
+#[derive(Deserialize)]
+struct SecretKeyRequest {
+    secret: String,
+}
+
 #[derive(Deserialize)]
 struct CreateTokenRequest {
     #[serde(rename = "mintAuthority")]
     mint_authority: String,
     mint: String,
     decimals: u8,
+    #[serde(rename = "freezeAuthority")]
+    freeze_authority: Option<String>,
+    #[serde(rename = "tokenProgram")]
+    token_program: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct CreateTokenFullRequest {
+    payer: String,
+    #[serde(flatten)]
+    token: CreateTokenRequest,
 }
 
 #[derive(Deserialize)]
@@ -92,122 +544,4713 @@ struct MintTokenRequest {
     mint: String,
     destination: String,
     authority: String,
-    amount: u64,
+    amount: Option<AmountInput>,
+    #[serde(rename = "uiAmount")]
+    ui_amount: Option<String>,
+    decimals: Option<u8>,
+    #[serde(default)]
+    signers: Vec<String>,
+    #[serde(rename = "tokenProgram")]
+    token_program: Option<String>,
 }
 
 #[derive(Deserialize)]
-struct SignMessageRequest {
-    message: String,
-    secret: String,
+struct MintTokenCheckedRequest {
+    mint: String,
+    destination: String,
+    authority: String,
+    amount: u64,
+    decimals: u8,
+    #[serde(default)]
+    signers: Vec<String>,
+    #[serde(rename = "tokenProgram")]
+    token_program: Option<String>,
 }
 
 #[derive(Deserialize)]
-struct VerifyMessageRequest {
-    message: String,
-    signature: String,
-    pubkey: String,
+struct CloseAccountRequest {
+    account: String,
+    destination: String,
+    owner: String,
+    #[serde(rename = "tokenProgram")]
+    token_program: Option<String>,
 }
 
 #[derive(Deserialize)]
-struct SendSolRequest {
-    from: String,
-    to: String,
-    lamports: u64,
+struct BurnRequest {
+    mint: String,
+    account: String,
+    authority: String,
+    amount: u64,
+    #[serde(default)]
+    signers: Vec<String>,
+    #[serde(rename = "tokenProgram")]
+    token_program: Option<String>,
 }
 
 #[derive(Deserialize)]
-struct SendTokenRequest {
-    destination: String,
+struct BurnCheckedRequest {
+    account: String,
     mint: String,
-    owner: String,
+    authority: String,
     amount: u64,
+    decimals: u8,
+    #[serde(default)]
+    signers: Vec<String>,
+    #[serde(rename = "tokenProgram")]
+    token_program: Option<String>,
 }
 
-fn error_response(message: &str) -> impl IntoResponse {
-    let response = ErrorResponse {
-        success: false,
-        error: message.to_string(),
-    };
-    (StatusCode::BAD_REQUEST, Json(response))
+#[derive(Deserialize)]
+struct SolanaPayRequest {
+    recipient: String,
+    amount: Option<String>,
+    #[serde(rename = "splToken")]
+    spl_token: Option<String>,
+    #[serde(default)]
+    reference: Vec<String>,
+    label: Option<String>,
+    message: Option<String>,
 }
 
-async fn root_handler() -> impl IntoResponse {
-    let response = ApiResponse {
-        success: true,
-        data: MessageData {
-            message: "gm gm".to_string(),
-        },
-    };
-    (StatusCode::OK, Json(response))
+#[derive(Deserialize)]
+struct SolanaPayQrQuery {
+    recipient: String,
+    amount: Option<String>,
+    #[serde(rename = "splToken")]
+    spl_token: Option<String>,
+    label: Option<String>,
+    message: Option<String>,
+    size: Option<u32>,
+    #[serde(default)]
+    format: QrFormat,
 }
 
-async fn keypair_handler() -> impl IntoResponse {
-    let keypair = Keypair::new();
-    let pubkey = keypair.pubkey().to_string(); 
-    let secret = bs58::encode(keypair.to_bytes()).into_string(); 
+#[derive(Deserialize, Clone, Copy, Default)]
+#[serde(rename_all = "lowercase")]
+enum QrFormat {
+    #[default]
+    Png,
+    Svg,
+}
 
-    let response = ApiResponse {
-        success: true,
-        data: KeypairData { pubkey, secret },
-    };
+#[derive(Deserialize)]
+struct FingerprintQuery {
+    pubkey: String,
+}
 
-    (StatusCode::OK, Json(response))
+#[derive(Deserialize)]
+struct ValidatePubkeyQuery {
+    #[serde(default)]
+    value: String,
 }
 
-async fn create_token_handler(Json(payload): Json<CreateTokenRequest>) -> impl IntoResponse {
-    let accounts = vec![
-        AccountMeta {
-            pubkey: payload.mint.clone(),
-            is_signer: false,
-            is_writable: true,
-        },
-        AccountMeta {
-            pubkey: payload.mint_authority.clone(),
-            is_signer: true,
-            is_writable: false,
-        },
-    ];
+#[derive(Deserialize)]
+struct AtaQuery {
+    owner: String,
+    mint: String,
+    #[serde(rename = "tokenProgram")]
+    token_program: Option<String>,
+}
 
-    let instruction_data = InstructionData {
-        program_id: "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA".to_string(),
-        accounts,
-        instruction_data: base64::engine::general_purpose::STANDARD.encode(&[0, payload.decimals]),
-    };
+#[derive(Deserialize)]
+struct CreateAtaRequest {
+    payer: String,
+    owner: String,
+    mint: String,
+    #[serde(rename = "tokenProgram")]
+    token_program: Option<String>,
+}
 
-    let response = ApiResponse {
-        success: true,
-        data: instruction_data,
-    };
+#[derive(Deserialize)]
+struct MemoRequest {
+    memo: String,
+    #[serde(default)]
+    signers: Vec<String>,
+}
 
-    (StatusCode::OK, Json(response))
+#[derive(Deserialize)]
+struct ComputeBudgetRequest {
+    #[serde(rename = "unitLimit")]
+    unit_limit: Option<u32>,
+    #[serde(rename = "unitPrice")]
+    unit_price: Option<u64>,
 }
 
-async fn mint_token_handler(Json(payload): Json<MintTokenRequest>) -> impl IntoResponse {
-    let accounts = vec![
-        AccountMeta {
-            pubkey: payload.mint.clone(),
-            is_signer: false,
-            is_writable: true,
-        },
-        AccountMeta {
-            pubkey: payload.destination.clone(),
-            is_signer: false,
-            is_writable: true,
-        },
-        AccountMeta {
-            pubkey: payload.authority.clone(),
-            is_signer: true,
-            is_writable: false,
-        },
-    ];
+#[derive(Deserialize)]
+struct LamportsToSolQuery {
+    lamports: String,
+}
 
-    let mut instruction_bytes = vec![7u8];
-    instruction_bytes.extend_from_slice(&payload.amount.to_le_bytes());
+#[derive(Deserialize)]
+struct SolToLamportsQuery {
+    sol: String,
+}
 
-    let instruction_data = InstructionData {
-        program_id: "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA".to_string(),
-        accounts,
-        instruction_data: base64::engine::general_purpose::STANDARD.encode(&instruction_bytes),
+#[derive(Deserialize)]
+struct PriorityFeeQuery {
+    accounts: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TokenAmountQuery {
+    #[serde(rename = "uiAmount")]
+    ui_amount: String,
+    decimals: u8,
+}
+
+#[derive(Deserialize)]
+struct RentQuery {
+    bytes: Option<String>,
+    account: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TransactionRequest {
+    transaction: String,
+}
+
+#[derive(Deserialize)]
+struct InstructionSpec {
+    #[serde(alias = "programId")]
+    program_id: String,
+    #[serde(default)]
+    accounts: Vec<AccountMeta>,
+    #[serde(alias = "data")]
+    instruction_data: String,
+}
+
+#[derive(Deserialize)]
+struct BuildTransactionRequest {
+    #[serde(rename = "feePayer")]
+    fee_payer: String,
+    #[serde(rename = "recentBlockhash")]
+    recent_blockhash: Option<String>,
+    instructions: Vec<InstructionSpec>,
+    #[serde(default)]
+    version: TransactionVersion,
+    #[serde(rename = "lookupTables", default)]
+    lookup_tables: Vec<LookupTableSpec>,
+}
+
+#[derive(Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum TransactionVersion {
+    #[default]
+    Legacy,
+    V0,
+}
+
+#[derive(Deserialize)]
+struct LookupTableSpec {
+    address: String,
+    addresses: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct TransactionSizeRequest {
+    #[serde(rename = "feePayer")]
+    fee_payer: String,
+    instructions: Vec<InstructionSpec>,
+}
+
+#[derive(Deserialize)]
+struct AirdropRequest {
+    pubkey: String,
+    lamports: u64,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "lowercase")]
+enum SeedSpec {
+    String(String),
+    Base64(String),
+    Pubkey(String),
+}
+
+#[derive(Deserialize)]
+struct PdaRequest {
+    #[serde(rename = "programId")]
+    program_id: String,
+    seeds: Vec<SeedSpec>,
+}
+
+#[derive(Deserialize)]
+struct DecodeInstructionRequest {
+    #[serde(rename = "programId")]
+    program_id: String,
+    data: String,
+}
+
+#[derive(Deserialize)]
+struct Base58EncodeRequest {
+    bytes: String,
+}
+
+#[derive(Deserialize)]
+struct Base58DecodeRequest {
+    value: String,
+}
+
+#[derive(Deserialize)]
+struct HashRequest {
+    data: String,
+    algorithm: String,
+}
+
+#[derive(Deserialize)]
+struct ApproveRequest {
+    source: String,
+    delegate: String,
+    owner: String,
+    amount: u64,
+    #[serde(rename = "tokenProgram")]
+    token_program: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RevokeRequest {
+    source: String,
+    owner: String,
+    #[serde(rename = "tokenProgram")]
+    token_program: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct SyncNativeRequest {
+    account: String,
+    #[serde(rename = "tokenProgram")]
+    token_program: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ParseAccountDataRequest {
+    data: String,
+}
+
+#[derive(Deserialize)]
+struct CreateMultisigRequest {
+    multisig: String,
+    signers: Vec<String>,
+    m: u8,
+    #[serde(rename = "tokenProgram")]
+    token_program: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct FreezeAccountRequest {
+    account: String,
+    mint: String,
+    #[serde(rename = "freezeAuthority")]
+    freeze_authority: String,
+    #[serde(rename = "tokenProgram")]
+    token_program: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum AuthorityTypeSpec {
+    MintTokens,
+    FreezeAccount,
+    AccountOwner,
+    CloseAccount,
+}
+
+impl From<AuthorityTypeSpec> for spl_token::instruction::AuthorityType {
+    fn from(spec: AuthorityTypeSpec) -> Self {
+        match spec {
+            AuthorityTypeSpec::MintTokens => spl_token::instruction::AuthorityType::MintTokens,
+            AuthorityTypeSpec::FreezeAccount => {
+                spl_token::instruction::AuthorityType::FreezeAccount
+            }
+            AuthorityTypeSpec::AccountOwner => spl_token::instruction::AuthorityType::AccountOwner,
+            AuthorityTypeSpec::CloseAccount => spl_token::instruction::AuthorityType::CloseAccount,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct SetAuthorityRequest {
+    account: String,
+    #[serde(rename = "currentAuthority")]
+    current_authority: String,
+    #[serde(rename = "authorityType")]
+    authority_type: AuthorityTypeSpec,
+    #[serde(rename = "newAuthority")]
+    new_authority: Option<String>,
+    #[serde(rename = "tokenProgram")]
+    token_program: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct CreateAccountRequest {
+    from: String,
+    #[serde(rename = "newAccount")]
+    new_account: String,
+    lamports: u64,
+    space: u64,
+    owner: String,
+}
+
+#[derive(Deserialize)]
+struct CreateAccountWithSeedRequest {
+    from: String,
+    #[serde(rename = "newAccount")]
+    new_account: String,
+    base: String,
+    seed: String,
+    lamports: AmountInput,
+    space: u64,
+    owner: String,
+}
+
+#[derive(Deserialize)]
+struct CreateNonceRequest {
+    from: String,
+    #[serde(rename = "nonceAccount")]
+    nonce_account: String,
+    authority: String,
+    lamports: AmountInput,
+}
+
+#[derive(Deserialize)]
+struct AdvanceNonceRequest {
+    #[serde(rename = "nonceAccount")]
+    nonce_account: String,
+    authority: String,
+}
+
+#[derive(Deserialize)]
+struct CreateLookupTableRequest {
+    authority: String,
+    payer: String,
+    #[serde(rename = "recentSlot")]
+    recent_slot: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct ExtendLookupTableRequest {
+    #[serde(rename = "lookupTable")]
+    lookup_table: String,
+    authority: String,
+    payer: String,
+    addresses: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct AllocateRequest {
+    account: String,
+    space: u64,
+}
+
+#[derive(Deserialize)]
+struct AssignRequest {
+    account: String,
+    owner: String,
+}
+
+#[derive(Deserialize)]
+struct DelegateStakeRequest {
+    #[serde(rename = "stakeAccount")]
+    stake_account: String,
+    #[serde(rename = "voteAccount")]
+    vote_account: String,
+    authority: String,
+}
+
+#[derive(Deserialize)]
+struct SignMessageRequest {
+    message: String,
+    secret: String,
+    #[serde(default)]
+    encoding: MessageEncoding,
+}
+
+#[derive(Deserialize)]
+struct SignMessageQuery {
+    #[serde(default)]
+    verbose: bool,
+}
+
+#[derive(Deserialize)]
+struct BatchSignMessageRequest {
+    secret: String,
+    messages: Vec<String>,
+    #[serde(default)]
+    encoding: MessageEncoding,
+}
+
+#[derive(Deserialize)]
+struct VerifyMessageRequest {
+    message: String,
+    signature: String,
+    pubkey: String,
+    #[serde(default)]
+    encoding: MessageEncoding,
+}
+
+#[derive(Deserialize, Clone, Copy, Default)]
+#[serde(rename_all = "lowercase")]
+enum MessageEncoding {
+    #[default]
+    Utf8,
+    Base64,
+    Hex,
+}
+
+impl MessageEncoding {
+    fn decode(self, message: &str) -> Result<Vec<u8>, ErrorResponse> {
+        match self {
+            MessageEncoding::Utf8 => Ok(message.as_bytes().to_vec()),
+            MessageEncoding::Base64 => base64::engine::general_purpose::STANDARD
+                .decode(message)
+                .map_err(|err| {
+                    ErrorResponse::new(ErrorCode::InvalidInput, "Invalid base64 message")
+                        .with_details(err)
+                }),
+            MessageEncoding::Hex => decode_hex(message)
+                .ok_or_else(|| ErrorResponse::new(ErrorCode::InvalidInput, "Invalid hex message")),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AmountInput {
+    Number(u64),
+    String(String),
+}
+
+impl AmountInput {
+    fn parse(&self) -> Result<u64, ErrorResponse> {
+        let invalid = || ErrorResponse::new(ErrorCode::InvalidAmount, "Invalid amount");
+
+        match self {
+            AmountInput::Number(value) => Ok(*value),
+            AmountInput::String(value) => {
+                if value.is_empty() || !value.bytes().all(|b| b.is_ascii_digit()) {
+                    return Err(invalid());
+                }
+                value.parse().map_err(|_| invalid())
+            }
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct SendSolRequest {
+    from: String,
+    to: String,
+    lamports: AmountInput,
+}
+
+#[derive(Deserialize)]
+struct BulkSendSolRequest {
+    from: String,
+    recipients: Vec<BulkRecipient>,
+}
+
+#[derive(Deserialize)]
+struct BulkRecipient {
+    to: String,
+    lamports: AmountInput,
+}
+
+#[derive(Deserialize)]
+struct SendSolTransactionRequest {
+    from: String,
+    to: String,
+    lamports: AmountInput,
+    #[serde(rename = "feePayer")]
+    fee_payer: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct SendSolWithSeedRequest {
+    from: String,
+    #[serde(rename = "fromBase")]
+    from_base: String,
+    #[serde(rename = "fromSeed")]
+    from_seed: String,
+    #[serde(rename = "fromOwner")]
+    from_owner: String,
+    to: String,
+    lamports: AmountInput,
+}
+
+#[derive(Deserialize)]
+struct SendTokenRequest {
+    source: String,
+    destination: String,
+    mint: String,
+    owner: String,
+    amount: Option<AmountInput>,
+    #[serde(rename = "uiAmount")]
+    ui_amount: Option<String>,
+    decimals: Option<u8>,
+    #[serde(default)]
+    signers: Vec<String>,
+    #[serde(rename = "tokenProgram")]
+    token_program: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct SendTokenCheckedRequest {
+    source: String,
+    destination: String,
+    mint: String,
+    owner: String,
+    amount: u64,
+    decimals: u8,
+    #[serde(rename = "tokenProgram")]
+    token_program: Option<String>,
+}
+
+#[derive(Deserialize, Clone, Copy, Default)]
+#[serde(rename_all = "lowercase")]
+enum DataEncoding {
+    #[default]
+    Base64,
+    Base58,
+}
+
+impl DataEncoding {
+    fn encode(self, bytes: &[u8]) -> String {
+        match self {
+            DataEncoding::Base64 => base64::engine::general_purpose::STANDARD.encode(bytes),
+            DataEncoding::Base58 => bs58::encode(bytes).into_string(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct EncodingQuery {
+    #[serde(default)]
+    encoding: DataEncoding,
+}
+
+fn instruction_to_data(instruction: Instruction, encoding: DataEncoding) -> InstructionData {
+    let accounts = instruction
+        .accounts
+        .iter()
+        .map(|meta| AccountMeta {
+            pubkey: meta.pubkey.to_string(),
+            is_signer: meta.is_signer,
+            is_writable: meta.is_writable,
+        })
+        .collect();
+
+    InstructionData {
+        program_id: instruction.program_id.to_string(),
+        accounts,
+        instruction_data: encoding.encode(&instruction.data),
+    }
+}
+
+fn merge_duplicate_account_metas(mut accounts: Vec<AccountMeta>) -> Vec<AccountMeta> {
+    // Instruction accounts are positional, so duplicates keep their slot and
+    // only share the union of their signer/writable flags.
+    for i in 0..accounts.len() {
+        let (is_signer, is_writable) = accounts
+            .iter()
+            .filter(|other| other.pubkey == accounts[i].pubkey)
+            .fold((false, false), |(signer, writable), other| {
+                (signer || other.is_signer, writable || other.is_writable)
+            });
+        accounts[i].is_signer = is_signer;
+        accounts[i].is_writable = is_writable;
+    }
+
+    accounts
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn decode_hex(input: &str) -> Option<Vec<u8>> {
+    if !input.len().is_multiple_of(2) || !input.is_ascii() {
+        return None;
+    }
+
+    (0..input.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&input[i..i + 2], 16).ok())
+        .collect()
+}
+
+fn parse_secret_key(secret: &str) -> Result<Keypair, ErrorResponse> {
+    let secret = secret.trim();
+    let invalid = || ErrorResponse::new(ErrorCode::InvalidSecretKey, "Invalid secret key");
+
+    let bytes = serde_json::from_str::<Vec<u8>>(secret)
+        .ok()
+        .or_else(|| {
+            if secret.len() == 128 || secret.len() == 64 {
+                decode_hex(secret)
+            } else {
+                None
+            }
+        })
+        .or_else(|| bs58::decode(secret).into_vec().ok())
+        .ok_or_else(invalid)?;
+
+    match bytes.len() {
+        64 => {
+            // Keypair::from_bytes trusts the public half, so derive it from the
+            // seed and reject secrets whose halves don't belong together.
+            let keypair = keypair_from_seed(&bytes[..32]).map_err(|_| invalid())?;
+            if keypair.pubkey().as_ref() != &bytes[32..] {
+                return Err(ErrorResponse::new(
+                    ErrorCode::InvalidSecretKey,
+                    "Public key does not match the secret key",
+                ));
+            }
+            Ok(keypair)
+        }
+        32 => keypair_from_seed(&bytes).map_err(|_| invalid()),
+        len => Err(ErrorResponse::new(
+            ErrorCode::InvalidSecretKey,
+            format!("Secret key must be 64 bytes, got {}", len),
+        )),
+    }
+}
+
+const TOKEN_2022_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+
+fn parse_pubkey(field: &str, value: &str) -> Result<Pubkey, ErrorResponse> {
+    if value.is_empty() {
+        return Err(ErrorResponse::new(
+            ErrorCode::MissingFields,
+            format!("Missing required field '{}'", field),
+        ));
+    }
+
+    value.parse::<Pubkey>().map_err(|err| {
+        ErrorResponse::new(
+            ErrorCode::InvalidPubkey,
+            format!("Invalid pubkey for field '{}'", field),
+        )
+        .with_details(err)
+    })
+}
+
+fn token_authority_metas(
+    authority: &str,
+    signers: &[String],
+) -> Result<Vec<AccountMeta>, ErrorResponse> {
+    if signers.len() > spl_token::instruction::MAX_SIGNERS {
+        return Err(ErrorResponse::new(
+            ErrorCode::LimitExceeded,
+            "Too many signers, maximum is 11",
+        ));
+    }
+
+    let mut accounts = vec![AccountMeta {
+        pubkey: authority.to_string(),
+        is_signer: signers.is_empty(),
+        is_writable: false,
+    }];
+
+    for signer in signers {
+        parse_pubkey("signers", signer)?;
+        accounts.push(AccountMeta {
+            pubkey: signer.clone(),
+            is_signer: true,
+            is_writable: false,
+        });
+    }
+
+    Ok(accounts)
+}
+
+fn parse_optional_pubkey(
+    field: &str,
+    value: Option<&str>,
+) -> Result<Option<Pubkey>, ErrorResponse> {
+    value.map(|value| parse_pubkey(field, value)).transpose()
+}
+
+fn resolve_token_program(value: Option<&str>) -> Result<Pubkey, &'static str> {
+    match value {
+        None | Some("token") | Some("spl-token") => Ok(spl_token::id()),
+        Some("token-2022") | Some("token2022") => Ok(TOKEN_2022_PROGRAM_ID),
+        Some(value) => value.parse::<Pubkey>().map_err(|_| "Invalid token program"),
+    }
+}
+
+fn keypair_data(keypair: &Keypair) -> KeypairData {
+    KeypairData {
+        pubkey: keypair.pubkey().to_string(),
+        secret: SecretData::Base58(bs58::encode(keypair.to_bytes()).into_string()),
+    }
+}
+
+fn decode_transaction(encoded: &str) -> Result<VersionedTransaction, &'static str> {
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(encoded)
+        .map_err(|_| "Invalid transaction encoding")?;
+
+    let transaction: VersionedTransaction =
+        bincode::deserialize(&bytes).map_err(|_| "Invalid transaction")?;
+
+    transaction.sanitize().map_err(|_| "Invalid transaction")?;
+
+    Ok(transaction)
+}
+
+fn parse_instruction_spec(index: usize, spec: &InstructionSpec) -> Result<Instruction, String> {
+    let program_id = spec
+        .program_id
+        .parse::<Pubkey>()
+        .map_err(|_| format!("Invalid program id in instruction {}", index))?;
+
+    let mut accounts = Vec::with_capacity(spec.accounts.len());
+    for account in &spec.accounts {
+        let pubkey = account
+            .pubkey
+            .parse::<Pubkey>()
+            .map_err(|_| format!("Invalid account address in instruction {}", index))?;
+
+        accounts.push(solana_sdk::instruction::AccountMeta {
+            pubkey,
+            is_signer: account.is_signer,
+            is_writable: account.is_writable,
+        });
+    }
+
+    let data = base64::engine::general_purpose::STANDARD
+        .decode(&spec.instruction_data)
+        .map_err(|_| format!("Invalid instruction data in instruction {}", index))?;
+
+    Ok(Instruction {
+        program_id,
+        accounts,
+        data,
+    })
+}
+
+fn parse_instruction_specs(specs: &[InstructionSpec]) -> Result<Vec<Instruction>, String> {
+    specs
+        .iter()
+        .enumerate()
+        .map(|(index, spec)| parse_instruction_spec(index, spec))
+        .collect()
+}
+
+const MAX_MESSAGE_ACCOUNT_KEYS: usize = 256;
+
+fn check_message_account_keys(
+    fee_payer: &Pubkey,
+    instructions: &[Instruction],
+) -> Result<(), ErrorResponse> {
+    // Legacy message compilation panics when a header count passes 255 and
+    // truncates account indices past 256 keys, so refuse those inputs up front.
+    let mut keys = HashSet::from([*fee_payer]);
+    let mut signers = HashSet::from([*fee_payer]);
+
+    for instruction in instructions {
+        keys.insert(instruction.program_id);
+        for meta in &instruction.accounts {
+            keys.insert(meta.pubkey);
+            if meta.is_signer {
+                signers.insert(meta.pubkey);
+            }
+        }
+    }
+
+    if keys.len() > MAX_MESSAGE_ACCOUNT_KEYS || signers.len() > u8::MAX as usize {
+        return Err(ErrorResponse::new(
+            ErrorCode::LimitExceeded,
+            format!(
+                "Too many unique account keys, maximum is {}",
+                MAX_MESSAGE_ACCOUNT_KEYS
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+const MAX_LOOKUP_TABLE_ADDRESSES: usize = 256;
+
+fn parse_lookup_tables(
+    specs: &[LookupTableSpec],
+) -> Result<Vec<AddressLookupTableAccount>, ErrorResponse> {
+    specs
+        .iter()
+        .enumerate()
+        .map(|(index, spec)| {
+            let key = parse_pubkey(&format!("lookupTables[{}].address", index), &spec.address)?;
+
+            if spec.addresses.len() > MAX_LOOKUP_TABLE_ADDRESSES {
+                return Err(ErrorResponse::new(
+                    ErrorCode::LimitExceeded,
+                    format!(
+                        "Lookup table {} has more than {} addresses",
+                        index, MAX_LOOKUP_TABLE_ADDRESSES
+                    ),
+                ));
+            }
+
+            let addresses = spec
+                .addresses
+                .iter()
+                .enumerate()
+                .map(|(i, address)| {
+                    parse_pubkey(
+                        &format!("lookupTables[{}].addresses[{}]", index, i),
+                        address,
+                    )
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            Ok(AddressLookupTableAccount { key, addresses })
+        })
+        .collect()
+}
+
+fn format_sol(lamports: u64) -> String {
+    let whole = lamports / LAMPORTS_PER_SOL;
+    let fraction = lamports % LAMPORTS_PER_SOL;
+
+    if fraction == 0 {
+        return whole.to_string();
+    }
+
+    let fraction = format!("{:09}", fraction);
+    format!("{}.{}", whole, fraction.trim_end_matches('0'))
+}
+
+fn parse_sol(input: &str) -> Result<u64, &'static str> {
+    parse_ui_amount(input, 9)
+}
+
+impl ErrorCode {
+    fn status(self) -> StatusCode {
+        match self {
+            ErrorCode::InvalidRequest
+            | ErrorCode::MissingFields
+            | ErrorCode::InvalidPubkey
+            | ErrorCode::InvalidSecretKey
+            | ErrorCode::InvalidSignature
+            | ErrorCode::InvalidAmount
+            | ErrorCode::AmountZero
+            | ErrorCode::InvalidTransaction
+            | ErrorCode::InvalidInput
+            | ErrorCode::LimitExceeded => StatusCode::BAD_REQUEST,
+            ErrorCode::Unauthorized => StatusCode::UNAUTHORIZED,
+            ErrorCode::NotFound => StatusCode::NOT_FOUND,
+            ErrorCode::Timeout => StatusCode::GATEWAY_TIMEOUT,
+            ErrorCode::Overloaded => StatusCode::SERVICE_UNAVAILABLE,
+            ErrorCode::RpcError => StatusCode::BAD_GATEWAY,
+            ErrorCode::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+impl ErrorResponse {
+    fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        ErrorResponse {
+            success: false,
+            code,
+            error: message.into(),
+            request_id: REQUEST_ID.try_with(|id| id.clone()).ok(),
+            details: None,
+        }
+    }
+
+    fn with_details(mut self, details: impl fmt::Display) -> Self {
+        if debug_errors_enabled() {
+            self.details = Some(details.to_string());
+        }
+        self
+    }
+}
+
+fn debug_errors_enabled() -> bool {
+    static ENABLED: std::sync::OnceLock<bool> = std::sync::OnceLock::new();
+    *ENABLED.get_or_init(|| {
+        matches!(
+            std::env::var("DEBUG_ERRORS").as_deref(),
+            Ok("1") | Ok("true")
+        )
+    })
+}
+
+impl IntoResponse for ErrorResponse {
+    fn into_response(self) -> Response {
+        (self.code.status(), Json(self)).into_response()
+    }
+}
+
+const MAX_TOKEN_DECIMALS: u8 = 9;
+
+fn validate_decimals(decimals: u8) -> Result<u8, ErrorResponse> {
+    if decimals > MAX_TOKEN_DECIMALS {
+        return Err(ErrorResponse::new(
+            ErrorCode::InvalidInput,
+            "Decimals must be between 0 and 9",
+        ));
+    }
+
+    Ok(decimals)
+}
+
+fn parse_ui_amount(input: &str, decimals: u8) -> Result<u64, &'static str> {
+    let input = input.trim();
+    let input = input.strip_prefix('+').unwrap_or(input);
+    let (whole, fraction) = input.split_once('.').unwrap_or((input, ""));
+
+    if whole.is_empty() && fraction.is_empty() {
+        return Err("Invalid amount");
+    }
+
+    if !whole.chars().all(|c| c.is_ascii_digit()) || !fraction.chars().all(|c| c.is_ascii_digit()) {
+        return Err("Invalid amount");
+    }
+
+    if fraction.len() > decimals as usize {
+        return Err("Amount has more decimal places than decimals allows");
+    }
+
+    let scale = 10u64
+        .checked_pow(decimals as u32)
+        .ok_or("Decimals is too large")?;
+
+    let whole: u64 = if whole.is_empty() {
+        0
+    } else {
+        whole.parse().map_err(|_| "Amount is too large")?
+    };
+
+    let fraction: u64 = if fraction.is_empty() {
+        0
+    } else {
+        format!("{:0<width$}", fraction, width = decimals as usize)
+            .parse()
+            .map_err(|_| "Invalid amount")?
+    };
+
+    whole
+        .checked_mul(scale)
+        .and_then(|amount| amount.checked_add(fraction))
+        .ok_or("Amount is too large")
+}
+
+fn percent_encode(input: &str) -> String {
+    let mut encoded = String::with_capacity(input.len());
+    for byte in input.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+fn solana_pay_url(request: &SolanaPayRequest) -> Result<String, ErrorResponse> {
+    let recipient = parse_pubkey("recipient", &request.recipient)?;
+    let mut params = Vec::new();
+
+    if let Some(amount) = request.amount.as_deref() {
+        let amount = amount.trim();
+        match parse_ui_amount(amount, MAX_TOKEN_DECIMALS) {
+            Ok(0) => {
+                return Err(ErrorResponse::new(
+                    ErrorCode::AmountZero,
+                    "Amount must be greater than 0",
+                ))
+            }
+            Ok(_) => {}
+            Err(message) => return Err(ErrorResponse::new(ErrorCode::InvalidAmount, message)),
+        }
+
+        // The spec requires a leading zero and no trailing decimal point.
+        let amount = amount.trim_end_matches('.');
+        let amount = if amount.starts_with('.') {
+            format!("0{}", amount)
+        } else {
+            amount.to_string()
+        };
+        params.push(format!("amount={}", amount));
+    }
+
+    if let Some(mint) = parse_optional_pubkey("splToken", request.spl_token.as_deref())? {
+        params.push(format!("spl-token={}", mint));
+    }
+
+    for (index, reference) in request.reference.iter().enumerate() {
+        let reference = parse_pubkey(&format!("reference[{}]", index), reference)?;
+        params.push(format!("reference={}", reference));
+    }
+
+    if let Some(label) = request.label.as_deref() {
+        params.push(format!("label={}", percent_encode(label)));
+    }
+
+    if let Some(message) = request.message.as_deref() {
+        params.push(format!("message={}", percent_encode(message)));
+    }
+
+    if params.is_empty() {
+        Ok(format!("solana:{}", recipient))
+    } else {
+        Ok(format!("solana:{}?{}", recipient, params.join("&")))
+    }
+}
+
+fn resolve_token_amount(
+    amount: Option<&AmountInput>,
+    ui_amount: Option<&str>,
+    decimals: Option<u8>,
+) -> Result<u64, ErrorResponse> {
+    match (amount, ui_amount, decimals) {
+        (Some(_), Some(_), _) => Err(ErrorResponse::new(
+            ErrorCode::InvalidInput,
+            "Provide either amount or uiAmount, not both",
+        )),
+        (Some(_), None, Some(_)) => Err(ErrorResponse::new(
+            ErrorCode::InvalidInput,
+            "decimals only applies to uiAmount; amount is already in base units",
+        )),
+        (Some(amount), None, None) => amount.parse(),
+        (None, Some(ui_amount), Some(decimals)) => {
+            parse_ui_amount(ui_amount, validate_decimals(decimals)?)
+                .map_err(|message| ErrorResponse::new(ErrorCode::InvalidAmount, message))
+        }
+        (None, Some(_), None) => Err(ErrorResponse::new(
+            ErrorCode::MissingFields,
+            "decimals is required with uiAmount",
+        )),
+        (None, None, _) => Err(ErrorResponse::new(
+            ErrorCode::MissingFields,
+            "Missing amount or uiAmount",
+        )),
+    }
+}
+
+fn error_response(code: ErrorCode, message: &str) -> impl IntoResponse {
+    error_response_with_status(code.status(), code, message)
+}
+
+fn error_response_with_status(
+    status: StatusCode,
+    code: ErrorCode,
+    message: &str,
+) -> impl IntoResponse {
+    (status, Json(ErrorResponse::new(code, message)))
+}
+
+struct JsonBody<T>(T);
+
+#[async_trait]
+impl<T, S> FromRequest<S> for JsonBody<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection> {
+        match Json::<T>::from_request(request, state).await {
+            Ok(Json(value)) => Ok(JsonBody(value)),
+            Err(rejection) => Err(json_rejection_response(rejection)),
+        }
+    }
+}
+
+fn json_rejection_response(rejection: JsonRejection) -> Response {
+    if let JsonRejection::MissingJsonContentType(_) = rejection {
+        return error_response_with_status(
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            ErrorCode::InvalidRequest,
+            "Content-Type must be application/json",
+        )
+        .into_response();
+    }
+
+    error_response_with_status(
+        rejection.status(),
+        ErrorCode::InvalidRequest,
+        &rejection.body_text(),
+    )
+    .into_response()
+}
+
+async fn root_handler() -> impl IntoResponse {
+    let response = ApiResponse {
+        success: true,
+        data: MessageData {
+            message: "gm gm".to_string(),
+        },
+    };
+    (StatusCode::OK, Json(response))
+}
+
+async fn not_found_handler() -> impl IntoResponse {
+    error_response(ErrorCode::NotFound, "Not found")
+}
+
+async fn health_handler(State(state): State<AppState>) -> impl IntoResponse {
+    let response = ApiResponse {
+        success: true,
+        data: HealthData {
+            status: "ok".to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            cluster: state.config.cluster.name().to_string(),
+            uptime_seconds: state.started_at.elapsed().as_secs(),
+        },
+    };
+    (StatusCode::OK, Json(response))
+}
+
+async fn rpc_health_handler(State(state): State<AppState>) -> impl IntoResponse {
+    let started = Instant::now();
+    let result = state.rpc_client.get_health().await;
+    let latency_ms = started.elapsed().as_millis() as u64;
+
+    if let Err(err) = result {
+        tracing::warn!("get_health failed: {}", err);
+        return error_response_with_status(
+            StatusCode::SERVICE_UNAVAILABLE,
+            ErrorCode::RpcError,
+            &format!("RPC node is unreachable (checked in {} ms)", latency_ms),
+        )
+        .into_response();
+    }
+
+    let response = ApiResponse {
+        success: true,
+        data: RpcHealthData {
+            status: "ok".to_string(),
+            cluster: state.config.cluster.name().to_string(),
+            latency_ms,
+        },
+    };
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn version_handler() -> impl IntoResponse {
+    let response = ApiResponse {
+        success: true,
+        data: VersionData {
+            name: env!("CARGO_PKG_NAME").to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            solana_sdk: env!("SOLANA_SDK_VERSION").to_string(),
+        },
+    };
+    (StatusCode::OK, Json(response))
+}
+
+async fn keypair_handler(Query(query): Query<KeypairQuery>) -> impl IntoResponse {
+    let keypair = Keypair::new();
+    let data = match query.format.as_deref() {
+        None | Some("base58") => keypair_data(&keypair),
+        Some("json") => KeypairData {
+            pubkey: keypair.pubkey().to_string(),
+            secret: SecretData::Bytes(keypair.to_bytes().to_vec()),
+        },
+        Some(_) => {
+            return error_response(
+                ErrorCode::InvalidInput,
+                "Invalid format, expected base58 or json",
+            )
+            .into_response()
+        }
+    };
+
+    let response = ApiResponse {
+        success: true,
+        data,
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+const MAX_BATCH_KEYPAIRS: u16 = 1000;
+
+async fn batch_keypair_handler(
+    JsonBody(payload): JsonBody<BatchKeypairRequest>,
+) -> impl IntoResponse {
+    if payload.count == 0 || payload.count > MAX_BATCH_KEYPAIRS {
+        return error_response(ErrorCode::InvalidInput, "Count must be between 1 and 1000")
+            .into_response();
+    }
+
+    let keypairs = match tokio::task::spawn_blocking(move || {
+        (0..payload.count)
+            .map(|_| keypair_data(&Keypair::new()))
+            .collect::<Vec<_>>()
+    })
+    .await
+    {
+        Ok(keypairs) => keypairs,
+        Err(_) => {
+            return error_response(ErrorCode::InternalError, "Failed to generate keypairs")
+                .into_response()
+        }
+    };
+
+    let response = ApiResponse {
+        success: true,
+        data: keypairs,
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const DEFAULT_VANITY_TIMEOUT_MS: u64 = 5_000;
+const MAX_VANITY_TIMEOUT_MS: u64 = 60_000;
+// Each extra character multiplies the expected attempts by roughly 58, so
+// anything past four or five characters will usually time out; this only
+// bounds how much work a single request can ask for.
+const MAX_VANITY_PREFIX_LEN: usize = 6;
+
+const VANITY_PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+const MAX_CONCURRENT_VANITY_SEARCHES: usize = 4;
+
+// Searches run on blocking threads that outlive the request (and the
+// WebSocket upgrade), so they share one pool of permits instead of relying
+// on the per-request limits.
+fn acquire_vanity_permit(state: &AppState) -> Result<OwnedSemaphorePermit, ErrorResponse> {
+    state
+        .vanity_searches
+        .clone()
+        .try_acquire_owned()
+        .map_err(|_| {
+            ErrorResponse::new(
+                ErrorCode::Overloaded,
+                "Too many vanity searches in progress, please retry shortly",
+            )
+        })
+}
+
+struct VanitySearch {
+    prefix: String,
+    case_sensitive: bool,
+    timeout: Duration,
+}
+
+impl VanitySearch {
+    fn from_request(payload: VanityKeypairRequest) -> Result<Self, ErrorResponse> {
+        if payload.prefix.is_empty() {
+            return Err(ErrorResponse::new(
+                ErrorCode::MissingFields,
+                "Missing required fields",
+            ));
+        }
+
+        if payload.prefix.chars().count() > MAX_VANITY_PREFIX_LEN {
+            return Err(ErrorResponse::new(
+                ErrorCode::LimitExceeded,
+                format!(
+                    "Prefix must be at most {} characters",
+                    MAX_VANITY_PREFIX_LEN
+                ),
+            ));
+        }
+
+        // Validate the prefix as typed: when case doesn't matter, a character only
+        // needs one of its cases in the alphabet (`l` can still match `L`).
+        let is_base58 = |c: char| {
+            BASE58_ALPHABET.contains(c)
+                || (!payload.case_sensitive
+                    && (BASE58_ALPHABET.contains(c.to_ascii_lowercase())
+                        || BASE58_ALPHABET.contains(c.to_ascii_uppercase())))
+        };
+        if !payload.prefix.chars().all(is_base58) {
+            return Err(ErrorResponse::new(
+                ErrorCode::InvalidInput,
+                "Prefix must only contain base58 characters",
+            ));
+        }
+
+        let timeout_ms = payload.timeout_ms.unwrap_or(DEFAULT_VANITY_TIMEOUT_MS);
+        if timeout_ms == 0 || timeout_ms > MAX_VANITY_TIMEOUT_MS {
+            return Err(ErrorResponse::new(
+                ErrorCode::InvalidInput,
+                "timeoutMs must be between 1 and 60000",
+            ));
+        }
+
+        let prefix = if payload.case_sensitive {
+            payload.prefix
+        } else {
+            payload.prefix.to_ascii_lowercase()
+        };
+
+        Ok(VanitySearch {
+            prefix,
+            case_sensitive: payload.case_sensitive,
+            timeout: Duration::from_millis(timeout_ms),
+        })
+    }
+
+    fn run(&self, mut on_progress: impl FnMut(u64, Duration) -> bool) -> Option<Keypair> {
+        let started_at = Instant::now();
+        let mut last_progress = started_at;
+        let mut attempts = 0u64;
+
+        while started_at.elapsed() < self.timeout {
+            let keypair = Keypair::new();
+            let pubkey = keypair.pubkey().to_string();
+            attempts += 1;
+
+            let matches = if self.case_sensitive {
+                pubkey.starts_with(&self.prefix)
+            } else {
+                pubkey.to_ascii_lowercase().starts_with(&self.prefix)
+            };
+
+            if matches {
+                return Some(keypair);
+            }
+
+            if last_progress.elapsed() >= VANITY_PROGRESS_INTERVAL {
+                last_progress = Instant::now();
+                if !on_progress(attempts, started_at.elapsed()) {
+                    return None;
+                }
+            }
+        }
+        None
+    }
+}
+
+async fn vanity_keypair_handler(
+    State(state): State<AppState>,
+    JsonBody(payload): JsonBody<VanityKeypairRequest>,
+) -> impl IntoResponse {
+    let search = match VanitySearch::from_request(payload) {
+        Ok(search) => search,
+        Err(error) => return error.into_response(),
+    };
+
+    let permit = match acquire_vanity_permit(&state) {
+        Ok(permit) => permit,
+        Err(error) => return error.into_response(),
+    };
+
+    let result = tokio::task::spawn_blocking(move || {
+        let _permit = permit;
+        search.run(|_, _| true)
+    })
+    .await;
+
+    let keypair = match result {
+        Ok(Some(keypair)) => keypair,
+        Ok(None) => {
+            return error_response(ErrorCode::Timeout, "Vanity search timed out").into_response()
+        }
+        Err(_) => {
+            return error_response(ErrorCode::InternalError, "Failed to generate keypair")
+                .into_response()
+        }
+    };
+
+    let response = ApiResponse {
+        success: true,
+        data: keypair_data(&keypair),
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum VanityStreamMessage {
+    Progress { attempts: u64, elapsed_ms: u64 },
+    Match { data: KeypairData },
+    Timeout,
+    Error { error: String },
+}
+
+async fn vanity_keypair_ws_handler(
+    State(state): State<AppState>,
+    ws: WebSocketUpgrade,
+    Query(query): Query<VanityKeypairRequest>,
+) -> Response {
+    let search = match VanitySearch::from_request(query) {
+        Ok(search) => search,
+        Err(error) => return error.into_response(),
+    };
+
+    let permit = match acquire_vanity_permit(&state) {
+        Ok(permit) => permit,
+        Err(error) => return error.into_response(),
+    };
+
+    ws.on_upgrade(move |socket| stream_vanity_search(socket, search, permit))
+}
+
+async fn send_vanity_message(socket: &mut WebSocket, message: &VanityStreamMessage) -> bool {
+    match serde_json::to_string(message) {
+        Ok(text) => socket.send(WsMessage::Text(text)).await.is_ok(),
+        Err(_) => false,
+    }
+}
+
+async fn stream_vanity_search(
+    mut socket: WebSocket,
+    search: VanitySearch,
+    permit: OwnedSemaphorePermit,
+) {
+    let (progress_tx, mut progress_rx) = tokio::sync::mpsc::channel(16);
+
+    let search_task = tokio::task::spawn_blocking(move || {
+        let _permit = permit;
+        search.run(|attempts, elapsed| {
+            progress_tx
+                .blocking_send(VanityStreamMessage::Progress {
+                    attempts,
+                    elapsed_ms: elapsed.as_millis() as u64,
+                })
+                .is_ok()
+        })
+    });
+
+    // Returning early drops the receiver, which makes the next progress send
+    // fail and stops the search.
+    loop {
+        tokio::select! {
+            progress = progress_rx.recv() => match progress {
+                Some(progress) => {
+                    if !send_vanity_message(&mut socket, &progress).await {
+                        return;
+                    }
+                }
+                None => break,
+            },
+            incoming = socket.recv() => match incoming {
+                Some(Ok(WsMessage::Close(_))) | Some(Err(_)) | None => return,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+
+    let outcome = match search_task.await {
+        Ok(Some(keypair)) => VanityStreamMessage::Match {
+            data: keypair_data(&keypair),
+        },
+        Ok(None) => VanityStreamMessage::Timeout,
+        Err(_) => VanityStreamMessage::Error {
+            error: "Failed to generate keypair".to_string(),
+        },
+    };
+
+    send_vanity_message(&mut socket, &outcome).await;
+    let _ = socket.close().await;
+}
+
+async fn keypair_from_mnemonic_handler(
+    JsonBody(payload): JsonBody<MnemonicKeypairRequest>,
+) -> impl IntoResponse {
+    let mnemonic = match Mnemonic::from_phrase(payload.mnemonic.trim(), Language::English) {
+        Ok(m) => m,
+        Err(_) => {
+            return error_response(ErrorCode::InvalidInput, "Invalid mnemonic").into_response()
+        }
+    };
+
+    let derivation_path = match payload.derivation_path.as_deref() {
+        Some(path) => match DerivationPath::from_absolute_path_str(path) {
+            Ok(dp) => dp,
+            Err(_) => {
+                return error_response(ErrorCode::InvalidInput, "Invalid derivation path")
+                    .into_response()
+            }
+        },
+        None => DerivationPath::new_bip44(Some(payload.account.unwrap_or(0)), Some(0)),
+    };
+
+    let seed = Seed::new(&mnemonic, &payload.passphrase);
+
+    let keypair =
+        match keypair_from_seed_and_derivation_path(seed.as_bytes(), Some(derivation_path)) {
+            Ok(kp) => kp,
+            Err(_) => {
+                return error_response(ErrorCode::InternalError, "Failed to derive keypair")
+                    .into_response()
+            }
+        };
+
+    let response = ApiResponse {
+        success: true,
+        data: keypair_data(&keypair),
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn keypair_from_bytes_handler(
+    JsonBody(payload): JsonBody<SecretKeyRequest>,
+) -> impl IntoResponse {
+    if payload.secret.is_empty() {
+        return error_response(ErrorCode::MissingFields, "Missing required field 'secret'")
+            .into_response();
+    }
+
+    let keypair = match parse_secret_key(&payload.secret) {
+        Ok(kp) => kp,
+        Err(error) => return error.into_response(),
+    };
+
+    let response = ApiResponse {
+        success: true,
+        data: keypair_data(&keypair),
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+fn initialize_mint_instruction(request: &CreateTokenRequest) -> Result<Instruction, ErrorResponse> {
+    let token_program = resolve_token_program(request.token_program.as_deref())
+        .map_err(|message| ErrorResponse::new(ErrorCode::InvalidPubkey, message))?;
+    let mint = parse_pubkey("mint", &request.mint)?;
+    let mint_authority = parse_pubkey("mintAuthority", &request.mint_authority)?;
+    validate_decimals(request.decimals)?;
+    let freeze_authority =
+        parse_optional_pubkey("freezeAuthority", request.freeze_authority.as_deref())?;
+
+    let mut instruction = spl_token::instruction::initialize_mint(
+        &spl_token::id(),
+        &mint,
+        &mint_authority,
+        freeze_authority.as_ref(),
+        request.decimals,
+    )
+    .map_err(|_| ErrorResponse::new(ErrorCode::InternalError, "Failed to build instruction"))?;
+    instruction.program_id = token_program;
+
+    Ok(instruction)
+}
+
+async fn create_token_handler(
+    Query(query): Query<EncodingQuery>,
+    JsonBody(payload): JsonBody<CreateTokenRequest>,
+) -> impl IntoResponse {
+    let instruction = match initialize_mint_instruction(&payload) {
+        Ok(ix) => ix,
+        Err(error) => return error.into_response(),
+    };
+
+    let response = ApiResponse {
+        success: true,
+        data: instruction_to_data(instruction, query.encoding),
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn create_token_full_handler(
+    Query(query): Query<EncodingQuery>,
+    JsonBody(payload): JsonBody<CreateTokenFullRequest>,
+) -> impl IntoResponse {
+    let payer = match parse_pubkey("payer", &payload.payer) {
+        Ok(pk) => pk,
+        Err(error) => return error.into_response(),
+    };
+
+    let initialize_mint = match initialize_mint_instruction(&payload.token) {
+        Ok(ix) => ix,
+        Err(error) => return error.into_response(),
+    };
+
+    let mint = initialize_mint.accounts[0].pubkey;
+    if payer == mint {
+        return error_response(
+            ErrorCode::InvalidInput,
+            "payer and mint must be different accounts",
+        )
+        .into_response();
+    }
+
+    let space = spl_token::state::Mint::LEN;
+    let create_account = solana_sdk::system_instruction::create_account(
+        &payer,
+        &mint,
+        Rent::default().minimum_balance(space),
+        space as u64,
+        &initialize_mint.program_id,
+    );
+
+    let instructions: Vec<InstructionData> = [create_account, initialize_mint]
+        .into_iter()
+        .map(|instruction| instruction_to_data(instruction, query.encoding))
+        .collect();
+
+    let response = ApiResponse {
+        success: true,
+        data: instructions,
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn mint_token_handler(
+    Query(query): Query<EncodingQuery>,
+    JsonBody(payload): JsonBody<MintTokenRequest>,
+) -> impl IntoResponse {
+    let token_program = match resolve_token_program(payload.token_program.as_deref()) {
+        Ok(pk) => pk,
+        Err(message) => return error_response(ErrorCode::InvalidPubkey, message).into_response(),
+    };
+
+    if let Err(error) = parse_pubkey("mint", &payload.mint) {
+        return error.into_response();
+    }
+
+    if let Err(error) = parse_pubkey("destination", &payload.destination) {
+        return error.into_response();
+    }
+
+    if let Err(error) = parse_pubkey("authority", &payload.authority) {
+        return error.into_response();
+    }
+
+    let amount = match resolve_token_amount(
+        payload.amount.as_ref(),
+        payload.ui_amount.as_deref(),
+        payload.decimals,
+    ) {
+        Ok(amount) => amount,
+        Err(error) => return error.into_response(),
+    };
+
+    let authority_accounts = match token_authority_metas(&payload.authority, &payload.signers) {
+        Ok(accounts) => accounts,
+        Err(error) => return error.into_response(),
+    };
+
+    let mut accounts = vec![
+        AccountMeta {
+            pubkey: payload.mint.clone(),
+            is_signer: false,
+            is_writable: true,
+        },
+        AccountMeta {
+            pubkey: payload.destination.clone(),
+            is_signer: false,
+            is_writable: true,
+        },
+    ];
+    accounts.extend(authority_accounts);
+
+    let mut instruction_bytes = vec![7u8];
+    instruction_bytes.extend_from_slice(&amount.to_le_bytes());
+
+    let instruction_data = InstructionData {
+        program_id: token_program.to_string(),
+        accounts: merge_duplicate_account_metas(accounts),
+        instruction_data: query.encoding.encode(&instruction_bytes),
+    };
+
+    let response = ApiResponse {
+        success: true,
+        data: instruction_data,
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn mint_token_checked_handler(
+    Query(query): Query<EncodingQuery>,
+    JsonBody(payload): JsonBody<MintTokenCheckedRequest>,
+) -> impl IntoResponse {
+    let token_program = match resolve_token_program(payload.token_program.as_deref()) {
+        Ok(pk) => pk,
+        Err(message) => return error_response(ErrorCode::InvalidPubkey, message).into_response(),
+    };
+
+    if let Err(error) = parse_pubkey("mint", &payload.mint) {
+        return error.into_response();
+    }
+
+    if let Err(error) = parse_pubkey("destination", &payload.destination) {
+        return error.into_response();
+    }
+
+    if let Err(error) = parse_pubkey("authority", &payload.authority) {
+        return error.into_response();
+    }
+
+    if let Err(error) = validate_decimals(payload.decimals) {
+        return error.into_response();
+    }
+
+    if payload.amount == 0 {
+        return error_response(ErrorCode::AmountZero, "Amount must be greater than 0")
+            .into_response();
+    }
+
+    let authority_accounts = match token_authority_metas(&payload.authority, &payload.signers) {
+        Ok(accounts) => accounts,
+        Err(error) => return error.into_response(),
+    };
+
+    let mut accounts = vec![
+        AccountMeta {
+            pubkey: payload.mint.clone(),
+            is_signer: false,
+            is_writable: true,
+        },
+        AccountMeta {
+            pubkey: payload.destination.clone(),
+            is_signer: false,
+            is_writable: true,
+        },
+    ];
+    accounts.extend(authority_accounts);
+
+    let mut instruction_bytes = vec![14u8];
+    instruction_bytes.extend_from_slice(&payload.amount.to_le_bytes());
+    instruction_bytes.push(payload.decimals);
+
+    let instruction_data = InstructionData {
+        program_id: token_program.to_string(),
+        accounts: merge_duplicate_account_metas(accounts),
+        instruction_data: query.encoding.encode(&instruction_bytes),
+    };
+
+    let response = ApiResponse {
+        success: true,
+        data: instruction_data,
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn close_account_handler(
+    JsonBody(payload): JsonBody<CloseAccountRequest>,
+) -> impl IntoResponse {
+    let token_program = match resolve_token_program(payload.token_program.as_deref()) {
+        Ok(pk) => pk,
+        Err(message) => return error_response(ErrorCode::InvalidPubkey, message).into_response(),
+    };
+
+    if let Err(error) = parse_pubkey("account", &payload.account) {
+        return error.into_response();
+    }
+
+    if let Err(error) = parse_pubkey("destination", &payload.destination) {
+        return error.into_response();
+    }
+
+    if let Err(error) = parse_pubkey("owner", &payload.owner) {
+        return error.into_response();
+    }
+
+    let accounts = vec![
+        AccountMeta {
+            pubkey: payload.account.clone(),
+            is_signer: false,
+            is_writable: true,
+        },
+        AccountMeta {
+            pubkey: payload.destination.clone(),
+            is_signer: false,
+            is_writable: true,
+        },
+        AccountMeta {
+            pubkey: payload.owner.clone(),
+            is_signer: true,
+            is_writable: false,
+        },
+    ];
+
+    let instruction_data = InstructionData {
+        program_id: token_program.to_string(),
+        accounts: merge_duplicate_account_metas(accounts),
+        instruction_data: base64::engine::general_purpose::STANDARD.encode([9u8]),
+    };
+
+    let response = ApiResponse {
+        success: true,
+        data: instruction_data,
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn burn_token_handler(JsonBody(payload): JsonBody<BurnRequest>) -> impl IntoResponse {
+    let token_program = match resolve_token_program(payload.token_program.as_deref()) {
+        Ok(pk) => pk,
+        Err(message) => return error_response(ErrorCode::InvalidPubkey, message).into_response(),
+    };
+
+    if let Err(error) = parse_pubkey("mint", &payload.mint) {
+        return error.into_response();
+    }
+
+    if let Err(error) = parse_pubkey("account", &payload.account) {
+        return error.into_response();
+    }
+
+    if let Err(error) = parse_pubkey("authority", &payload.authority) {
+        return error.into_response();
+    }
+
+    if payload.amount == 0 {
+        return error_response(ErrorCode::AmountZero, "Amount must be greater than 0")
+            .into_response();
+    }
+
+    let authority_accounts = match token_authority_metas(&payload.authority, &payload.signers) {
+        Ok(accounts) => accounts,
+        Err(error) => return error.into_response(),
+    };
+
+    let mut accounts = vec![
+        AccountMeta {
+            pubkey: payload.account.clone(),
+            is_signer: false,
+            is_writable: true,
+        },
+        AccountMeta {
+            pubkey: payload.mint.clone(),
+            is_signer: false,
+            is_writable: true,
+        },
+    ];
+    accounts.extend(authority_accounts);
+
+    let mut instruction_bytes = vec![8u8];
+    instruction_bytes.extend_from_slice(&payload.amount.to_le_bytes());
+
+    let instruction_data = InstructionData {
+        program_id: token_program.to_string(),
+        accounts: merge_duplicate_account_metas(accounts),
+        instruction_data: base64::engine::general_purpose::STANDARD.encode(instruction_bytes),
+    };
+
+    let response = ApiResponse {
+        success: true,
+        data: instruction_data,
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn burn_token_checked_handler(
+    JsonBody(payload): JsonBody<BurnCheckedRequest>,
+) -> impl IntoResponse {
+    let token_program = match resolve_token_program(payload.token_program.as_deref()) {
+        Ok(pk) => pk,
+        Err(message) => return error_response(ErrorCode::InvalidPubkey, message).into_response(),
+    };
+
+    if let Err(error) = parse_pubkey("account", &payload.account) {
+        return error.into_response();
+    }
+
+    if let Err(error) = parse_pubkey("mint", &payload.mint) {
+        return error.into_response();
+    }
+
+    if let Err(error) = parse_pubkey("authority", &payload.authority) {
+        return error.into_response();
+    }
+
+    if let Err(error) = validate_decimals(payload.decimals) {
+        return error.into_response();
+    }
+
+    if payload.amount == 0 {
+        return error_response(ErrorCode::AmountZero, "Amount must be greater than 0")
+            .into_response();
+    }
+
+    let authority_accounts = match token_authority_metas(&payload.authority, &payload.signers) {
+        Ok(accounts) => accounts,
+        Err(error) => return error.into_response(),
+    };
+
+    let mut accounts = vec![
+        AccountMeta {
+            pubkey: payload.account.clone(),
+            is_signer: false,
+            is_writable: true,
+        },
+        AccountMeta {
+            pubkey: payload.mint.clone(),
+            is_signer: false,
+            is_writable: true,
+        },
+    ];
+    accounts.extend(authority_accounts);
+
+    let mut instruction_bytes = vec![15u8];
+    instruction_bytes.extend_from_slice(&payload.amount.to_le_bytes());
+    instruction_bytes.push(payload.decimals);
+
+    let instruction_data = InstructionData {
+        program_id: token_program.to_string(),
+        accounts: merge_duplicate_account_metas(accounts),
+        instruction_data: base64::engine::general_purpose::STANDARD.encode(instruction_bytes),
+    };
+
+    let response = ApiResponse {
+        success: true,
+        data: instruction_data,
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn approve_handler(JsonBody(payload): JsonBody<ApproveRequest>) -> impl IntoResponse {
+    let token_program = match resolve_token_program(payload.token_program.as_deref()) {
+        Ok(pk) => pk,
+        Err(message) => return error_response(ErrorCode::InvalidPubkey, message).into_response(),
+    };
+
+    if let Err(error) = parse_pubkey("source", &payload.source) {
+        return error.into_response();
+    }
+
+    if let Err(error) = parse_pubkey("delegate", &payload.delegate) {
+        return error.into_response();
+    }
+
+    if let Err(error) = parse_pubkey("owner", &payload.owner) {
+        return error.into_response();
+    }
+
+    if payload.amount == 0 {
+        return error_response(ErrorCode::AmountZero, "Amount must be greater than 0")
+            .into_response();
+    }
+
+    let accounts = vec![
+        AccountMeta {
+            pubkey: payload.source.clone(),
+            is_signer: false,
+            is_writable: true,
+        },
+        AccountMeta {
+            pubkey: payload.delegate.clone(),
+            is_signer: false,
+            is_writable: false,
+        },
+        AccountMeta {
+            pubkey: payload.owner.clone(),
+            is_signer: true,
+            is_writable: false,
+        },
+    ];
+
+    let mut instruction_bytes = vec![4u8];
+    instruction_bytes.extend_from_slice(&payload.amount.to_le_bytes());
+
+    let instruction_data = InstructionData {
+        program_id: token_program.to_string(),
+        accounts: merge_duplicate_account_metas(accounts),
+        instruction_data: base64::engine::general_purpose::STANDARD.encode(instruction_bytes),
+    };
+
+    let response = ApiResponse {
+        success: true,
+        data: instruction_data,
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn revoke_handler(JsonBody(payload): JsonBody<RevokeRequest>) -> impl IntoResponse {
+    let token_program = match resolve_token_program(payload.token_program.as_deref()) {
+        Ok(pk) => pk,
+        Err(message) => return error_response(ErrorCode::InvalidPubkey, message).into_response(),
+    };
+
+    if let Err(error) = parse_pubkey("source", &payload.source) {
+        return error.into_response();
+    }
+
+    if let Err(error) = parse_pubkey("owner", &payload.owner) {
+        return error.into_response();
+    }
+
+    let accounts = vec![
+        AccountMeta {
+            pubkey: payload.source.clone(),
+            is_signer: false,
+            is_writable: true,
+        },
+        AccountMeta {
+            pubkey: payload.owner.clone(),
+            is_signer: true,
+            is_writable: false,
+        },
+    ];
+
+    let instruction_data = InstructionData {
+        program_id: token_program.to_string(),
+        accounts: merge_duplicate_account_metas(accounts),
+        instruction_data: base64::engine::general_purpose::STANDARD.encode([5u8]),
+    };
+
+    let response = ApiResponse {
+        success: true,
+        data: instruction_data,
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn sync_native_handler(JsonBody(payload): JsonBody<SyncNativeRequest>) -> impl IntoResponse {
+    let token_program = match resolve_token_program(payload.token_program.as_deref()) {
+        Ok(pk) => pk,
+        Err(message) => return error_response(ErrorCode::InvalidPubkey, message).into_response(),
+    };
+
+    if let Err(error) = parse_pubkey("account", &payload.account) {
+        return error.into_response();
+    }
+
+    let accounts = vec![AccountMeta {
+        pubkey: payload.account.clone(),
+        is_signer: false,
+        is_writable: true,
+    }];
+
+    let instruction_data = InstructionData {
+        program_id: token_program.to_string(),
+        accounts,
+        instruction_data: base64::engine::general_purpose::STANDARD.encode([17u8]),
+    };
+
+    let response = ApiResponse {
+        success: true,
+        data: instruction_data,
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn parse_token_account_handler(
+    JsonBody(payload): JsonBody<ParseAccountDataRequest>,
+) -> impl IntoResponse {
+    let data = match base64::engine::general_purpose::STANDARD.decode(&payload.data) {
+        Ok(data) => data,
+        Err(err) => {
+            return ErrorResponse::new(ErrorCode::InvalidInput, "Invalid base64 data")
+                .with_details(err)
+                .into_response()
+        }
+    };
+
+    if data.len() != spl_token::state::Account::LEN {
+        return error_response(
+            ErrorCode::InvalidInput,
+            "Token account data must be 165 bytes",
+        )
+        .into_response();
+    }
+
+    let account = match spl_token::state::Account::unpack_unchecked(&data) {
+        Ok(account) => account,
+        Err(_) => {
+            return error_response(ErrorCode::InvalidInput, "Invalid token account data")
+                .into_response()
+        }
+    };
+
+    let state = match account.state {
+        spl_token::state::AccountState::Uninitialized => "uninitialized",
+        spl_token::state::AccountState::Initialized => "initialized",
+        spl_token::state::AccountState::Frozen => "frozen",
+    };
+
+    let response = ApiResponse {
+        success: true,
+        data: TokenAccountStateData {
+            mint: account.mint.to_string(),
+            owner: account.owner.to_string(),
+            amount: account.amount,
+            delegate: Option::from(account.delegate).map(|pk: Pubkey| pk.to_string()),
+            state,
+            is_native: Option::from(account.is_native),
+            delegated_amount: account.delegated_amount,
+            close_authority: Option::from(account.close_authority).map(|pk: Pubkey| pk.to_string()),
+        },
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn parse_mint_handler(
+    JsonBody(payload): JsonBody<ParseAccountDataRequest>,
+) -> impl IntoResponse {
+    let data = match base64::engine::general_purpose::STANDARD.decode(&payload.data) {
+        Ok(data) => data,
+        Err(err) => {
+            return ErrorResponse::new(ErrorCode::InvalidInput, "Invalid base64 data")
+                .with_details(err)
+                .into_response()
+        }
+    };
+
+    if data.len() != spl_token::state::Mint::LEN {
+        return error_response(ErrorCode::InvalidInput, "Mint data must be 82 bytes")
+            .into_response();
+    }
+
+    let mint = match spl_token::state::Mint::unpack_unchecked(&data) {
+        Ok(mint) => mint,
+        Err(_) => {
+            return error_response(ErrorCode::InvalidInput, "Invalid mint data").into_response()
+        }
+    };
+
+    let response = ApiResponse {
+        success: true,
+        data: MintStateData {
+            mint_authority: Option::from(mint.mint_authority).map(|pk: Pubkey| pk.to_string()),
+            supply: mint.supply,
+            decimals: mint.decimals,
+            is_initialized: mint.is_initialized,
+            freeze_authority: Option::from(mint.freeze_authority).map(|pk: Pubkey| pk.to_string()),
+        },
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn create_multisig_handler(
+    JsonBody(payload): JsonBody<CreateMultisigRequest>,
+) -> impl IntoResponse {
+    let token_program = match resolve_token_program(payload.token_program.as_deref()) {
+        Ok(pk) => pk,
+        Err(message) => return error_response(ErrorCode::InvalidPubkey, message).into_response(),
+    };
+
+    if let Err(error) = parse_pubkey("multisig", &payload.multisig) {
+        return error.into_response();
+    }
+
+    if payload.signers.is_empty() || payload.signers.len() > spl_token::instruction::MAX_SIGNERS {
+        return error_response(
+            ErrorCode::InvalidInput,
+            "Signers must contain between 1 and 11 pubkeys",
+        )
+        .into_response();
+    }
+
+    if payload.m == 0 || payload.m as usize > payload.signers.len() {
+        return error_response(
+            ErrorCode::InvalidInput,
+            "m must be between 1 and the number of signers",
+        )
+        .into_response();
+    }
+
+    let mut accounts = vec![
+        AccountMeta {
+            pubkey: payload.multisig.clone(),
+            is_signer: false,
+            is_writable: true,
+        },
+        AccountMeta {
+            pubkey: solana_sdk::sysvar::rent::id().to_string(),
+            is_signer: false,
+            is_writable: false,
+        },
+    ];
+
+    for signer in &payload.signers {
+        if let Err(error) = parse_pubkey("signers", signer) {
+            return error.into_response();
+        }
+        accounts.push(AccountMeta {
+            pubkey: signer.clone(),
+            is_signer: false,
+            is_writable: false,
+        });
+    }
+
+    let instruction_data = InstructionData {
+        program_id: token_program.to_string(),
+        accounts,
+        instruction_data: base64::engine::general_purpose::STANDARD.encode([2u8, payload.m]),
+    };
+
+    let response = ApiResponse {
+        success: true,
+        data: instruction_data,
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+fn freeze_instruction_response(payload: FreezeAccountRequest, opcode: u8) -> Response {
+    let token_program = match resolve_token_program(payload.token_program.as_deref()) {
+        Ok(pk) => pk,
+        Err(message) => return error_response(ErrorCode::InvalidPubkey, message).into_response(),
+    };
+
+    if let Err(error) = parse_pubkey("account", &payload.account) {
+        return error.into_response();
+    }
+
+    if let Err(error) = parse_pubkey("mint", &payload.mint) {
+        return error.into_response();
+    }
+
+    if let Err(error) = parse_pubkey("freezeAuthority", &payload.freeze_authority) {
+        return error.into_response();
+    }
+
+    let accounts = vec![
+        AccountMeta {
+            pubkey: payload.account,
+            is_signer: false,
+            is_writable: true,
+        },
+        AccountMeta {
+            pubkey: payload.mint,
+            is_signer: false,
+            is_writable: false,
+        },
+        AccountMeta {
+            pubkey: payload.freeze_authority,
+            is_signer: true,
+            is_writable: false,
+        },
+    ];
+
+    let instruction_data = InstructionData {
+        program_id: token_program.to_string(),
+        accounts: merge_duplicate_account_metas(accounts),
+        instruction_data: base64::engine::general_purpose::STANDARD.encode([opcode]),
+    };
+
+    let response = ApiResponse {
+        success: true,
+        data: instruction_data,
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn freeze_account_handler(
+    JsonBody(payload): JsonBody<FreezeAccountRequest>,
+) -> impl IntoResponse {
+    freeze_instruction_response(payload, 10)
+}
+
+async fn thaw_account_handler(
+    JsonBody(payload): JsonBody<FreezeAccountRequest>,
+) -> impl IntoResponse {
+    freeze_instruction_response(payload, 11)
+}
+
+async fn set_authority_handler(
+    JsonBody(payload): JsonBody<SetAuthorityRequest>,
+) -> impl IntoResponse {
+    let token_program = match resolve_token_program(payload.token_program.as_deref()) {
+        Ok(pk) => pk,
+        Err(message) => return error_response(ErrorCode::InvalidPubkey, message).into_response(),
+    };
+
+    let account = match parse_pubkey("account", &payload.account) {
+        Ok(pk) => pk,
+        Err(error) => return error.into_response(),
+    };
+
+    let current_authority = match parse_pubkey("currentAuthority", &payload.current_authority) {
+        Ok(pk) => pk,
+        Err(error) => return error.into_response(),
+    };
+
+    let new_authority =
+        match parse_optional_pubkey("newAuthority", payload.new_authority.as_deref()) {
+            Ok(pk) => pk,
+            Err(error) => return error.into_response(),
+        };
+
+    let mut instruction = match spl_token::instruction::set_authority(
+        &spl_token::id(),
+        &account,
+        new_authority.as_ref(),
+        payload.authority_type.into(),
+        &current_authority,
+        &[],
+    ) {
+        Ok(ix) => ix,
+        Err(_) => {
+            return error_response(ErrorCode::InternalError, "Failed to build instruction")
+                .into_response()
+        }
+    };
+    instruction.program_id = token_program;
+
+    let response = ApiResponse {
+        success: true,
+        data: instruction_to_data(instruction, DataEncoding::Base64),
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn fingerprint_handler(Query(query): Query<FingerprintQuery>) -> impl IntoResponse {
+    let pubkey = match parse_pubkey("pubkey", &query.pubkey) {
+        Ok(pk) => pk,
+        Err(error) => return error.into_response(),
+    };
+
+    let digest = solana_sdk::hash::hash(pubkey.as_ref()).to_bytes();
+
+    let response = ApiResponse {
+        success: true,
+        data: FingerprintData {
+            pubkey: pubkey.to_string(),
+            fingerprint: encode_hex(&digest[..4]),
+        },
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn validate_pubkey_handler(Query(query): Query<ValidatePubkeyQuery>) -> impl IntoResponse {
+    let pubkey = query.value.trim().parse::<Pubkey>().ok();
+
+    let response = ApiResponse {
+        success: true,
+        data: PubkeyValidationData {
+            valid: pubkey.is_some(),
+            on_curve: pubkey.is_some_and(|pk| pk.is_on_curve()),
+        },
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn solana_pay_url_handler(
+    JsonBody(payload): JsonBody<SolanaPayRequest>,
+) -> impl IntoResponse {
+    let url = match solana_pay_url(&payload) {
+        Ok(url) => url,
+        Err(error) => return error.into_response(),
+    };
+
+    let response = ApiResponse {
+        success: true,
+        data: SolanaPayUrlData { url },
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+const DEFAULT_QR_SIZE: u32 = 256;
+const MAX_QR_SIZE: u32 = 2048;
+
+// Solana Pay repeats `reference` once per key, which a flat query struct can't
+// hold, so references are collected from the raw pairs. A comma-separated list
+// in a single pair is still accepted.
+fn qr_references(params: &[(String, String)]) -> Vec<String> {
+    params
+        .iter()
+        .filter(|(key, _)| key == "reference")
+        .flat_map(|(_, value)| value.split(','))
+        .map(|reference| reference.trim().to_string())
+        .collect()
+}
+
+async fn solana_pay_qr_handler(
+    Query(query): Query<SolanaPayQrQuery>,
+    Query(params): Query<Vec<(String, String)>>,
+) -> Response {
+    let size = query.size.unwrap_or(DEFAULT_QR_SIZE);
+    if size == 0 || size > MAX_QR_SIZE {
+        return error_response(
+            ErrorCode::InvalidInput,
+            &format!("size must be between 1 and {}", MAX_QR_SIZE),
+        )
+        .into_response();
+    }
+
+    let request = SolanaPayRequest {
+        recipient: query.recipient,
+        amount: query.amount,
+        spl_token: query.spl_token,
+        reference: qr_references(&params),
+        label: query.label,
+        message: query.message,
+    };
+
+    let url = match solana_pay_url(&request) {
+        Ok(url) => url,
+        Err(error) => return error.into_response(),
+    };
+
+    let code = match qrcode::QrCode::new(url.as_bytes()) {
+        Ok(code) => code,
+        Err(err) => {
+            return ErrorResponse::new(
+                ErrorCode::LimitExceeded,
+                "URL is too long to encode as a QR code",
+            )
+            .with_details(err)
+            .into_response()
+        }
+    };
+
+    match query.format {
+        QrFormat::Svg => {
+            let svg = code
+                .render::<qrcode::render::svg::Color>()
+                .min_dimensions(size, size)
+                .build();
+            (
+                StatusCode::OK,
+                [(header::CONTENT_TYPE, "image/svg+xml")],
+                svg,
+            )
+                .into_response()
+        }
+        QrFormat::Png => {
+            let buffer = code
+                .render::<image::Luma<u8>>()
+                .min_dimensions(size, size)
+                .build();
+            let mut png = Vec::new();
+            if buffer
+                .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+                .is_err()
+            {
+                return error_response(ErrorCode::InternalError, "Failed to encode QR code")
+                    .into_response();
+            }
+            (StatusCode::OK, [(header::CONTENT_TYPE, "image/png")], png).into_response()
+        }
+    }
+}
+
+async fn ata_address_handler(Query(query): Query<AtaQuery>) -> impl IntoResponse {
+    let owner = match parse_pubkey("owner", &query.owner) {
+        Ok(pk) => pk,
+        Err(error) => return error.into_response(),
+    };
+
+    let mint = match parse_pubkey("mint", &query.mint) {
+        Ok(pk) => pk,
+        Err(error) => return error.into_response(),
+    };
+
+    let token_program = match resolve_token_program(query.token_program.as_deref()) {
+        Ok(pk) => pk,
+        Err(message) => return error_response(ErrorCode::InvalidPubkey, message).into_response(),
+    };
+
+    let address = spl_associated_token_account::get_associated_token_address_with_program_id(
+        &owner,
+        &mint,
+        &token_program,
+    );
+
+    let response = ApiResponse {
+        success: true,
+        data: AddressData {
+            address: address.to_string(),
+        },
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn create_ata_handler(JsonBody(payload): JsonBody<CreateAtaRequest>) -> impl IntoResponse {
+    let token_program = match resolve_token_program(payload.token_program.as_deref()) {
+        Ok(pk) => pk,
+        Err(message) => return error_response(ErrorCode::InvalidPubkey, message).into_response(),
+    };
+
+    let payer = match parse_pubkey("payer", &payload.payer) {
+        Ok(pk) => pk,
+        Err(error) => return error.into_response(),
+    };
+
+    let owner = match parse_pubkey("owner", &payload.owner) {
+        Ok(pk) => pk,
+        Err(error) => return error.into_response(),
+    };
+
+    let mint = match parse_pubkey("mint", &payload.mint) {
+        Ok(pk) => pk,
+        Err(error) => return error.into_response(),
+    };
+
+    let instruction = spl_associated_token_account::instruction::create_associated_token_account(
+        &payer,
+        &owner,
+        &mint,
+        &token_program,
+    );
+
+    let response = ApiResponse {
+        success: true,
+        data: instruction_to_data(instruction, DataEncoding::Base64),
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn memo_handler(JsonBody(payload): JsonBody<MemoRequest>) -> impl IntoResponse {
+    if payload.memo.is_empty() {
+        return error_response(ErrorCode::MissingFields, "Memo must not be empty").into_response();
+    }
+
+    let mut signers = Vec::with_capacity(payload.signers.len());
+    for signer in &payload.signers {
+        match parse_pubkey("signers", signer) {
+            Ok(pk) => signers.push(pk),
+            Err(error) => return error.into_response(),
+        }
+    }
+
+    let signer_refs: Vec<&Pubkey> = signers.iter().collect();
+    let instruction = spl_memo::build_memo(payload.memo.as_bytes(), &signer_refs);
+
+    let response = ApiResponse {
+        success: true,
+        data: instruction_to_data(instruction, DataEncoding::Base64),
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn compute_budget_handler(
+    JsonBody(payload): JsonBody<ComputeBudgetRequest>,
+) -> impl IntoResponse {
+    if payload.unit_limit.is_none() && payload.unit_price.is_none() {
+        return error_response(
+            ErrorCode::MissingFields,
+            "Either unitLimit or unitPrice is required",
+        )
+        .into_response();
+    }
+
+    let mut instructions = Vec::new();
+
+    if let Some(unit_limit) = payload.unit_limit {
+        let mut instruction_bytes = vec![2u8];
+        instruction_bytes.extend_from_slice(&unit_limit.to_le_bytes());
+
+        instructions.push(InstructionData {
+            program_id: "ComputeBudget111111111111111111111111111111".to_string(),
+            accounts: vec![],
+            instruction_data: base64::engine::general_purpose::STANDARD.encode(instruction_bytes),
+        });
+    }
+
+    if let Some(unit_price) = payload.unit_price {
+        let mut instruction_bytes = vec![3u8];
+        instruction_bytes.extend_from_slice(&unit_price.to_le_bytes());
+
+        instructions.push(InstructionData {
+            program_id: "ComputeBudget111111111111111111111111111111".to_string(),
+            accounts: vec![],
+            instruction_data: base64::engine::general_purpose::STANDARD.encode(instruction_bytes),
+        });
+    }
+
+    let response = ApiResponse {
+        success: true,
+        data: instructions,
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn lamports_to_sol_handler(Query(query): Query<LamportsToSolQuery>) -> impl IntoResponse {
+    let lamports = match query.lamports.parse::<u64>() {
+        Ok(lamports) => lamports,
+        Err(_) => {
+            return error_response(ErrorCode::InvalidAmount, "Invalid lamports amount")
+                .into_response()
+        }
+    };
+
+    let response = ApiResponse {
+        success: true,
+        data: SolData {
+            sol: format_sol(lamports),
+        },
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn sol_to_lamports_handler(Query(query): Query<SolToLamportsQuery>) -> impl IntoResponse {
+    let lamports = match parse_sol(&query.sol) {
+        Ok(lamports) => lamports,
+        Err(message) => return error_response(ErrorCode::InvalidAmount, message).into_response(),
+    };
+
+    let response = ApiResponse {
+        success: true,
+        data: LamportsData { lamports },
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn token_amount_handler(Query(query): Query<TokenAmountQuery>) -> impl IntoResponse {
+    if let Err(error) = validate_decimals(query.decimals) {
+        return error.into_response();
+    }
+
+    let amount = match parse_ui_amount(&query.ui_amount, query.decimals) {
+        Ok(amount) => amount,
+        Err(message) => return error_response(ErrorCode::InvalidAmount, message).into_response(),
+    };
+
+    let response = ApiResponse {
+        success: true,
+        data: TokenAmountData { amount },
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn rent_handler(Query(query): Query<RentQuery>) -> impl IntoResponse {
+    let bytes = match (query.bytes.as_deref(), query.account.as_deref()) {
+        (Some(bytes), None) => match bytes.parse::<usize>() {
+            Ok(bytes) if bytes as u64 <= MAX_PERMITTED_DATA_LENGTH => bytes,
+            Ok(_) => {
+                return error_response(
+                    ErrorCode::InvalidInput,
+                    &format!("bytes must be at most {}", MAX_PERMITTED_DATA_LENGTH),
+                )
+                .into_response()
+            }
+            Err(_) => {
+                return error_response(ErrorCode::InvalidInput, "Invalid bytes value")
+                    .into_response()
+            }
+        },
+        (None, Some("mint")) => spl_token::state::Mint::LEN,
+        (None, Some("token")) => spl_token::state::Account::LEN,
+        (None, Some("multisig")) => spl_token::state::Multisig::LEN,
+        (None, Some(_)) => {
+            return error_response(
+                ErrorCode::InvalidInput,
+                "Unknown account type, expected mint, token or multisig",
+            )
+            .into_response()
+        }
+        (Some(_), Some(_)) => {
+            return error_response(
+                ErrorCode::InvalidInput,
+                "Provide either bytes or account, not both",
+            )
+            .into_response()
+        }
+        (None, None) => {
+            return error_response(ErrorCode::MissingFields, "Missing bytes or account")
+                .into_response()
+        }
+    };
+
+    let response = ApiResponse {
+        success: true,
+        data: LamportsData {
+            lamports: Rent::default().minimum_balance(bytes),
+        },
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+fn check_message_length(message: &[u8], max_bytes: usize) -> Result<(), ErrorResponse> {
+    if message.len() > max_bytes {
+        return Err(ErrorResponse::new(
+            ErrorCode::LimitExceeded,
+            "Message too long",
+        ));
+    }
+
+    Ok(())
+}
+
+fn sign_with_keypair(
+    keypair: &Keypair,
+    message_bytes: &[u8],
+    message: String,
+) -> Option<SignatureData> {
+    let signature = keypair.try_sign_message(message_bytes).ok()?;
+
+    Some(SignatureData {
+        signature: base64::engine::general_purpose::STANDARD.encode(signature.as_ref()),
+        public_key: keypair.pubkey().to_string(),
+        message,
+        message_bytes_base64: None,
+        message_length: None,
+    })
+}
+
+const BLOCKHASH_CACHE_TTL: Duration = Duration::from_secs(1);
+
+async fn latest_blockhash(state: &AppState) -> Result<CachedBlockhash, ClientError> {
+    let mut cache = state.blockhash_cache.lock().await;
+
+    if let Some(cached) = *cache {
+        if cached.fetched_at.elapsed() < BLOCKHASH_CACHE_TTL {
+            return Ok(cached);
+        }
+    }
+
+    let (blockhash, last_valid_block_height) = state
+        .rpc_client
+        .get_latest_blockhash_with_commitment(state.rpc_client.commitment())
+        .await?;
+
+    let fetched = CachedBlockhash {
+        blockhash,
+        last_valid_block_height,
+        fetched_at: Instant::now(),
+    };
+    *cache = Some(fetched);
+
+    Ok(fetched)
+}
+
+async fn blockhash_handler(State(state): State<AppState>) -> impl IntoResponse {
+    let cached = match latest_blockhash(&state).await {
+        Ok(cached) => cached,
+        Err(err) => {
+            tracing::warn!("get_latest_blockhash failed: {}", err);
+            return error_response(
+                ErrorCode::RpcError,
+                "Failed to fetch recent blockhash from RPC",
+            )
+            .into_response();
+        }
+    };
+
+    let response = ApiResponse {
+        success: true,
+        data: BlockhashData {
+            blockhash: cached.blockhash.to_string(),
+            last_valid_block_height: cached.last_valid_block_height,
+        },
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn balance_handler(
+    State(state): State<AppState>,
+    Path(pubkey): Path<String>,
+) -> impl IntoResponse {
+    let pubkey = match pubkey.parse::<Pubkey>() {
+        Ok(pk) => pk,
+        Err(_) => {
+            return error_response(ErrorCode::InvalidPubkey, "Invalid public key").into_response()
+        }
+    };
+
+    let lamports = match state.rpc_client.get_balance(&pubkey).await {
+        Ok(lamports) => lamports,
+        Err(err) => {
+            tracing::warn!("get_balance failed for {}: {}", pubkey, err);
+            return error_response(ErrorCode::RpcError, "Failed to fetch balance from RPC")
+                .into_response();
+        }
+    };
+
+    let response = ApiResponse {
+        success: true,
+        data: BalanceData {
+            lamports,
+            sol: format_sol(lamports),
+        },
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn signature_status_handler(
+    State(state): State<AppState>,
+    Path(signature): Path<String>,
+) -> impl IntoResponse {
+    let signature = match signature.parse::<Signature>() {
+        Ok(sig) => sig,
+        Err(_) => {
+            return error_response(ErrorCode::InvalidSignature, "Invalid signature").into_response()
+        }
+    };
+
+    let status = match state.rpc_client.get_signature_statuses(&[signature]).await {
+        Ok(response) => response.value.into_iter().next().flatten(),
+        Err(err) => {
+            tracing::warn!("get_signature_statuses failed for {}: {}", signature, err);
+            return error_response(
+                ErrorCode::RpcError,
+                "Failed to fetch signature status from RPC",
+            )
+            .into_response();
+        }
+    };
+
+    let data = match status {
+        Some(status) => SignatureStatusData {
+            found: true,
+            slot: Some(status.slot),
+            confirmations: status.confirmations,
+            confirmation_status: status
+                .confirmation_status
+                .map(|confirmation| format!("{:?}", confirmation).to_lowercase()),
+            error: status.err.map(|err| err.to_string()),
+        },
+        None => SignatureStatusData {
+            found: false,
+            slot: None,
+            confirmations: None,
+            confirmation_status: None,
+            error: None,
+        },
+    };
+
+    let response = ApiResponse {
+        success: true,
+        data,
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+const MAX_PRIORITY_FEE_ACCOUNTS: usize = 128;
+
+async fn priority_fees_handler(
+    State(state): State<AppState>,
+    Query(query): Query<PriorityFeeQuery>,
+) -> impl IntoResponse {
+    let mut accounts = Vec::new();
+    for account in query
+        .accounts
+        .as_deref()
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+    {
+        if account.is_empty() {
+            continue;
+        }
+        match parse_pubkey("accounts", account) {
+            Ok(pk) => accounts.push(pk),
+            Err(error) => return error.into_response(),
+        }
+    }
+
+    if accounts.len() > MAX_PRIORITY_FEE_ACCOUNTS {
+        return error_response(
+            ErrorCode::LimitExceeded,
+            "Too many accounts, maximum is 128",
+        )
+        .into_response();
+    }
+
+    let mut fees: Vec<u64> = match state
+        .rpc_client
+        .get_recent_prioritization_fees(&accounts)
+        .await
+    {
+        Ok(fees) => fees.into_iter().map(|fee| fee.prioritization_fee).collect(),
+        Err(err) => {
+            tracing::warn!("get_recent_prioritization_fees failed: {}", err);
+            return error_response(
+                ErrorCode::RpcError,
+                "Failed to fetch prioritization fees from RPC",
+            )
+            .into_response();
+        }
+    };
+    fees.sort_unstable();
+
+    let response = ApiResponse {
+        success: true,
+        data: PriorityFeeData {
+            samples: fees.len(),
+            min: fees.first().copied(),
+            median: fees.get(fees.len() / 2).copied(),
+            max: fees.last().copied(),
+        },
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn decode_transaction_handler(
+    JsonBody(payload): JsonBody<TransactionRequest>,
+) -> impl IntoResponse {
+    if payload.transaction.is_empty() {
+        return error_response(ErrorCode::MissingFields, "Missing required fields").into_response();
+    }
+
+    let transaction = match decode_transaction(&payload.transaction) {
+        Ok(tx) => tx,
+        Err(message) => {
+            return error_response(ErrorCode::InvalidTransaction, message).into_response()
+        }
+    };
+
+    let message = &transaction.message;
+    let account_keys = message.static_account_keys();
+
+    // Sanitizing guarantees program ids are static keys; account indices may
+    // point into lookup tables and are returned unresolved.
+    let instructions: Vec<DecodedTransactionInstruction> = message
+        .instructions()
+        .iter()
+        .map(|instruction| DecodedTransactionInstruction {
+            program_id: account_keys[instruction.program_id_index as usize].to_string(),
+            accounts: instruction.accounts.clone(),
+            data_length: instruction.data.len(),
+        })
+        .collect();
+
+    let response = ApiResponse {
+        success: true,
+        data: DecodedTransactionData {
+            version: match message {
+                VersionedMessage::Legacy(_) => "legacy".to_string(),
+                VersionedMessage::V0(_) => "v0".to_string(),
+            },
+            fee_payer: account_keys[0].to_string(),
+            recent_blockhash: message.recent_blockhash().to_string(),
+            signatures: transaction
+                .signatures
+                .iter()
+                .map(|signature| signature.to_string())
+                .collect(),
+            account_keys: account_keys.iter().map(|key| key.to_string()).collect(),
+            lookup_tables: message
+                .address_table_lookups()
+                .unwrap_or_default()
+                .iter()
+                .map(|lookup| lookup.account_key.to_string())
+                .collect(),
+            instruction_count: instructions.len(),
+            instructions,
+        },
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn send_transaction_handler(
+    State(state): State<AppState>,
+    JsonBody(payload): JsonBody<TransactionRequest>,
+) -> impl IntoResponse {
+    if payload.transaction.is_empty() {
+        return error_response(ErrorCode::MissingFields, "Missing required fields").into_response();
+    }
+
+    let transaction = match decode_transaction(&payload.transaction) {
+        Ok(tx) => tx,
+        Err(message) => {
+            return error_response(ErrorCode::InvalidTransaction, message).into_response()
+        }
+    };
+
+    if transaction.verify_with_results().iter().any(|valid| !valid) {
+        return error_response(
+            ErrorCode::InvalidTransaction,
+            "Transaction is not fully signed",
+        )
+        .into_response();
+    }
+
+    let signature = match state.rpc_client.send_transaction(&transaction).await {
+        Ok(sig) => sig,
+        Err(err) => {
+            tracing::warn!("send_transaction failed: {}", err);
+            return ErrorResponse::new(ErrorCode::RpcError, "Failed to send transaction")
+                .with_details(err)
+                .into_response();
+        }
+    };
+
+    let response = ApiResponse {
+        success: true,
+        data: TransactionSignatureData {
+            signature: signature.to_string(),
+        },
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn simulate_transaction_handler(
+    State(state): State<AppState>,
+    JsonBody(payload): JsonBody<TransactionRequest>,
+) -> impl IntoResponse {
+    if payload.transaction.is_empty() {
+        return error_response(ErrorCode::MissingFields, "Missing required fields").into_response();
+    }
+
+    let transaction = match decode_transaction(&payload.transaction) {
+        Ok(tx) => tx,
+        Err(message) => {
+            return error_response(ErrorCode::InvalidTransaction, message).into_response()
+        }
+    };
+
+    let result = match state.rpc_client.simulate_transaction(&transaction).await {
+        Ok(response) => response.value,
+        Err(err) => {
+            tracing::warn!("simulate_transaction failed: {}", err);
+            return ErrorResponse::new(ErrorCode::RpcError, "Failed to simulate transaction")
+                .with_details(err)
+                .into_response();
+        }
+    };
+
+    let response = ApiResponse {
+        success: true,
+        data: SimulationData {
+            error: result.err.map(|err| err.to_string()),
+            logs: result.logs.unwrap_or_default(),
+            units_consumed: result.units_consumed,
+        },
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn build_transaction_handler(
+    State(state): State<AppState>,
+    JsonBody(payload): JsonBody<BuildTransactionRequest>,
+) -> impl IntoResponse {
+    if payload.instructions.is_empty() {
+        return error_response(
+            ErrorCode::MissingFields,
+            "At least one instruction is required",
+        )
+        .into_response();
+    }
+
+    let fee_payer = match parse_pubkey("feePayer", &payload.fee_payer) {
+        Ok(pk) => pk,
+        Err(error) => return error.into_response(),
+    };
+
+    let instructions = match parse_instruction_specs(&payload.instructions) {
+        Ok(instructions) => instructions,
+        Err(message) => return error_response(ErrorCode::InvalidInput, &message).into_response(),
+    };
+
+    if payload.version == TransactionVersion::Legacy && !payload.lookup_tables.is_empty() {
+        return error_response(
+            ErrorCode::InvalidInput,
+            "lookupTables require version \"v0\"",
+        )
+        .into_response();
+    }
+
+    let blockhash = match payload.recent_blockhash.as_deref() {
+        Some(blockhash) => match blockhash.parse::<Hash>() {
+            Ok(hash) => hash,
+            Err(_) => {
+                return error_response(ErrorCode::InvalidInput, "Invalid recent blockhash")
+                    .into_response()
+            }
+        },
+        None => match latest_blockhash(&state).await {
+            Ok(cached) => cached.blockhash,
+            Err(err) => {
+                tracing::warn!("get_latest_blockhash failed: {}", err);
+                return error_response(
+                    ErrorCode::RpcError,
+                    "Failed to fetch recent blockhash from RPC",
+                )
+                .into_response();
+            }
+        },
+    };
+
+    let (message_bytes, transaction_bytes) = match payload.version {
+        TransactionVersion::Legacy => {
+            if let Err(error) = check_message_account_keys(&fee_payer, &instructions) {
+                return error.into_response();
+            }
+
+            let message = Message::new_with_blockhash(&instructions, Some(&fee_payer), &blockhash);
+            let message_bytes = message.serialize();
+            (
+                message_bytes,
+                bincode::serialize(&Transaction::new_unsigned(message)),
+            )
+        }
+        TransactionVersion::V0 => {
+            let lookup_tables = match parse_lookup_tables(&payload.lookup_tables) {
+                Ok(tables) => tables,
+                Err(error) => return error.into_response(),
+            };
+
+            let message = match v0::Message::try_compile(
+                &fee_payer,
+                &instructions,
+                &lookup_tables,
+                blockhash,
+            ) {
+                Ok(message) => VersionedMessage::V0(message),
+                Err(err) => {
+                    return ErrorResponse::new(
+                        ErrorCode::InvalidTransaction,
+                        "Failed to compile v0 message",
+                    )
+                    .with_details(err)
+                    .into_response()
+                }
+            };
+            let message_bytes = message.serialize();
+            let transaction = VersionedTransaction {
+                signatures: vec![
+                    Signature::default();
+                    message.header().num_required_signatures as usize
+                ],
+                message,
+            };
+            (message_bytes, bincode::serialize(&transaction))
+        }
+    };
+
+    let transaction_bytes = match transaction_bytes {
+        Ok(bytes) => bytes,
+        Err(_) => {
+            return error_response(ErrorCode::InternalError, "Failed to serialize transaction")
+                .into_response()
+        }
+    };
+
+    let response = ApiResponse {
+        success: true,
+        data: BuiltTransactionData {
+            transaction: base64::engine::general_purpose::STANDARD.encode(transaction_bytes),
+            message: base64::engine::general_purpose::STANDARD.encode(message_bytes),
+            blockhash: blockhash.to_string(),
+            version: match payload.version {
+                TransactionVersion::Legacy => "legacy".to_string(),
+                TransactionVersion::V0 => "v0".to_string(),
+            },
+        },
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn transaction_size_handler(
+    JsonBody(payload): JsonBody<TransactionSizeRequest>,
+) -> impl IntoResponse {
+    if payload.instructions.is_empty() {
+        return error_response(
+            ErrorCode::MissingFields,
+            "At least one instruction is required",
+        )
+        .into_response();
+    }
+
+    let fee_payer = match parse_pubkey("feePayer", &payload.fee_payer) {
+        Ok(pk) => pk,
+        Err(error) => return error.into_response(),
+    };
+
+    let instructions = match parse_instruction_specs(&payload.instructions) {
+        Ok(instructions) => instructions,
+        Err(message) => return error_response(ErrorCode::InvalidInput, &message).into_response(),
+    };
+
+    if let Err(error) = check_message_account_keys(&fee_payer, &instructions) {
+        return error.into_response();
+    }
+
+    // The blockhash is a fixed 32 bytes, so any value gives the same size.
+    let message = Message::new_with_blockhash(&instructions, Some(&fee_payer), &Hash::default());
+    let message_size = message.serialize().len();
+    let transaction = Transaction::new_unsigned(message);
+
+    let size = match bincode::serialized_size(&transaction) {
+        Ok(size) => size as usize,
+        Err(_) => {
+            return error_response(ErrorCode::InternalError, "Failed to serialize transaction")
+                .into_response()
+        }
+    };
+
+    let response = ApiResponse {
+        success: true,
+        data: TransactionSizeData {
+            size,
+            message_size,
+            signatures: transaction.signatures.len(),
+            max_size: PACKET_DATA_SIZE,
+            exceeds_limit: size > PACKET_DATA_SIZE,
+        },
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+const MAX_AIRDROP_LAMPORTS: u64 = 2 * LAMPORTS_PER_SOL;
+
+async fn airdrop_handler(
+    State(state): State<AppState>,
+    JsonBody(payload): JsonBody<AirdropRequest>,
+) -> impl IntoResponse {
+    if matches!(state.config.cluster, Cluster::Mainnet) {
+        return error_response(ErrorCode::InvalidInput, "Airdrop not available on mainnet")
+            .into_response();
+    }
+
+    let pubkey = match parse_pubkey("pubkey", &payload.pubkey) {
+        Ok(pk) => pk,
+        Err(error) => return error.into_response(),
+    };
+
+    if payload.lamports == 0 {
+        return error_response(ErrorCode::AmountZero, "Amount must be greater than 0")
+            .into_response();
+    }
+
+    if payload.lamports > MAX_AIRDROP_LAMPORTS {
+        return error_response(
+            ErrorCode::LimitExceeded,
+            "Amount exceeds the airdrop limit of 2 SOL",
+        )
+        .into_response();
+    }
+
+    let signature = match state
+        .rpc_client
+        .request_airdrop(&pubkey, payload.lamports)
+        .await
+    {
+        Ok(sig) => sig,
+        Err(err) => {
+            tracing::warn!("request_airdrop failed for {}: {}", pubkey, err);
+            return ErrorResponse::new(ErrorCode::RpcError, "Failed to request airdrop")
+                .with_details(err)
+                .into_response();
+        }
+    };
+
+    let response = ApiResponse {
+        success: true,
+        data: TransactionSignatureData {
+            signature: signature.to_string(),
+        },
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+fn decode_seed(seed: &SeedSpec) -> Result<Vec<u8>, &'static str> {
+    match seed {
+        SeedSpec::String(value) => Ok(value.as_bytes().to_vec()),
+        SeedSpec::Base64(value) => base64::engine::general_purpose::STANDARD
+            .decode(value)
+            .map_err(|_| "Invalid base64 seed"),
+        SeedSpec::Pubkey(value) => value
+            .parse::<Pubkey>()
+            .map(|pk| pk.to_bytes().to_vec())
+            .map_err(|_| "Invalid pubkey seed"),
+    }
+}
+
+async fn pda_handler(JsonBody(payload): JsonBody<PdaRequest>) -> impl IntoResponse {
+    let program_id = match parse_pubkey("programId", &payload.program_id) {
+        Ok(pk) => pk,
+        Err(error) => return error.into_response(),
+    };
+
+    // One slot is reserved for the bump seed appended by find_program_address.
+    if payload.seeds.len() >= MAX_SEEDS {
+        return error_response(ErrorCode::LimitExceeded, "Too many seeds, maximum is 15")
+            .into_response();
+    }
+
+    let mut seeds = Vec::with_capacity(payload.seeds.len());
+    for seed in &payload.seeds {
+        let bytes = match decode_seed(seed) {
+            Ok(bytes) => bytes,
+            Err(message) => {
+                return error_response(ErrorCode::InvalidInput, message).into_response()
+            }
+        };
+
+        if bytes.len() > MAX_SEED_LEN {
+            return error_response(ErrorCode::LimitExceeded, "Seed exceeds the 32 byte limit")
+                .into_response();
+        }
+
+        seeds.push(bytes);
+    }
+
+    let seed_refs: Vec<&[u8]> = seeds.iter().map(Vec::as_slice).collect();
+    let (address, bump) = match Pubkey::try_find_program_address(&seed_refs, &program_id) {
+        Some(found) => found,
+        None => {
+            return error_response(
+                ErrorCode::InvalidInput,
+                "Unable to find a viable program address",
+            )
+            .into_response()
+        }
+    };
+
+    let response = ApiResponse {
+        success: true,
+        data: PdaData {
+            address: address.to_string(),
+            bump,
+        },
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn decode_instruction_handler(
+    JsonBody(payload): JsonBody<DecodeInstructionRequest>,
+) -> impl IntoResponse {
+    let program_id = match parse_pubkey("programId", &payload.program_id) {
+        Ok(pk) => pk,
+        Err(error) => return error.into_response(),
+    };
+
+    let data = match base64::engine::general_purpose::STANDARD.decode(&payload.data) {
+        Ok(data) => data,
+        Err(err) => {
+            return ErrorResponse::new(ErrorCode::InvalidInput, "Invalid base64 data")
+                .with_details(err)
+                .into_response()
+        }
+    };
+
+    let decoded = if program_id == spl_token::id() || program_id == TOKEN_2022_PROGRAM_ID {
+        DecodedInstructionData {
+            program: if program_id == spl_token::id() {
+                "spl-token"
+            } else {
+                "spl-token-2022"
+            },
+            opcode: data.first().map(|opcode| *opcode as u32),
+            instruction: spl_token::instruction::TokenInstruction::unpack(&data)
+                .ok()
+                .map(|instruction| format!("{:?}", instruction)),
+            data_length: data.len(),
+        }
+    } else if program_id == system_program::id() {
+        DecodedInstructionData {
+            program: "system",
+            opcode: data
+                .get(..4)
+                .map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])),
+            instruction: bincode::deserialize::<solana_sdk::system_instruction::SystemInstruction>(
+                &data,
+            )
+            .ok()
+            .map(|instruction| format!("{:?}", instruction)),
+            data_length: data.len(),
+        }
+    } else {
+        DecodedInstructionData {
+            program: "unknown",
+            opcode: data.first().map(|opcode| *opcode as u32),
+            instruction: None,
+            data_length: data.len(),
+        }
+    };
+
+    let response = ApiResponse {
+        success: true,
+        data: decoded,
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+// Base58 conversion is quadratic in the input length, so both directions are
+// capped well below the body limit. 1024 bytes encode to at most 1399 chars.
+const MAX_BASE58_BYTES: usize = 1024;
+const MAX_BASE58_CHARS: usize = 1399;
+
+async fn base58_encode_handler(
+    JsonBody(payload): JsonBody<Base58EncodeRequest>,
+) -> impl IntoResponse {
+    let bytes = match base64::engine::general_purpose::STANDARD.decode(&payload.bytes) {
+        Ok(bytes) => bytes,
+        Err(err) => {
+            return ErrorResponse::new(ErrorCode::InvalidInput, "Invalid base64 bytes")
+                .with_details(err)
+                .into_response()
+        }
+    };
+
+    if bytes.len() > MAX_BASE58_BYTES {
+        return error_response(
+            ErrorCode::LimitExceeded,
+            &format!("bytes must decode to at most {} bytes", MAX_BASE58_BYTES),
+        )
+        .into_response();
+    }
+
+    let response = ApiResponse {
+        success: true,
+        data: Base58Data {
+            value: bs58::encode(bytes).into_string(),
+        },
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn base58_decode_handler(
+    JsonBody(payload): JsonBody<Base58DecodeRequest>,
+) -> impl IntoResponse {
+    if payload.value.is_empty() {
+        return error_response(ErrorCode::MissingFields, "Missing required fields").into_response();
+    }
+
+    if payload.value.len() > MAX_BASE58_CHARS {
+        return error_response(
+            ErrorCode::LimitExceeded,
+            &format!("value must be at most {} characters", MAX_BASE58_CHARS),
+        )
+        .into_response();
+    }
+
+    let bytes = match bs58::decode(&payload.value).into_vec() {
+        Ok(bytes) => bytes,
+        Err(err) => {
+            return ErrorResponse::new(ErrorCode::InvalidInput, "Invalid base58 value")
+                .with_details(err)
+                .into_response()
+        }
+    };
+
+    let response = ApiResponse {
+        success: true,
+        data: BytesData {
+            length: bytes.len(),
+            bytes: base64::engine::general_purpose::STANDARD.encode(bytes),
+        },
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn hash_handler(JsonBody(payload): JsonBody<HashRequest>) -> impl IntoResponse {
+    let data = match base64::engine::general_purpose::STANDARD.decode(&payload.data) {
+        Ok(data) => data,
+        Err(err) => {
+            return ErrorResponse::new(ErrorCode::InvalidInput, "Invalid base64 data")
+                .with_details(err)
+                .into_response()
+        }
+    };
+
+    let digest = match payload.algorithm.as_str() {
+        "sha256" => solana_sdk::hash::hash(&data).to_bytes(),
+        "keccak256" => solana_sdk::keccak::hash(&data).to_bytes(),
+        _ => {
+            return error_response(
+                ErrorCode::InvalidInput,
+                "Invalid algorithm, expected sha256 or keccak256",
+            )
+            .into_response()
+        }
+    };
+
+    let response = ApiResponse {
+        success: true,
+        data: HashData {
+            algorithm: payload.algorithm,
+            hex: encode_hex(&digest),
+            base64: base64::engine::general_purpose::STANDARD.encode(digest),
+        },
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn sign_message_handler(
+    State(state): State<AppState>,
+    Query(query): Query<SignMessageQuery>,
+    JsonBody(payload): JsonBody<SignMessageRequest>,
+) -> impl IntoResponse {
+    if payload.message.is_empty() || payload.secret.is_empty() {
+        return error_response(ErrorCode::MissingFields, "Missing required fields").into_response();
+    }
+
+    let message_bytes = match payload.encoding.decode(&payload.message) {
+        Ok(bytes) => bytes,
+        Err(error) => return error.into_response(),
+    };
+
+    if let Err(error) = check_message_length(&message_bytes, state.config.max_message_bytes) {
+        return error.into_response();
+    }
+
+    let keypair = match parse_secret_key(&payload.secret) {
+        Ok(kp) => kp,
+        Err(error) => return error.into_response(),
+    };
+
+    let mut response_data = match sign_with_keypair(&keypair, &message_bytes, payload.message) {
+        Some(data) => data,
+        None => {
+            return error_response(ErrorCode::InternalError, "Failed to sign message")
+                .into_response()
+        }
+    };
+
+    if query.verbose {
+        response_data.message_bytes_base64 =
+            Some(base64::engine::general_purpose::STANDARD.encode(&message_bytes));
+        response_data.message_length = Some(message_bytes.len());
+    }
+
+    let response = ApiResponse {
+        success: true,
+        data: response_data,
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn sign_offchain_message_handler(
+    State(state): State<AppState>,
+    JsonBody(payload): JsonBody<SignMessageRequest>,
+) -> impl IntoResponse {
+    if payload.message.is_empty() || payload.secret.is_empty() {
+        return error_response(ErrorCode::MissingFields, "Missing required fields").into_response();
+    }
+
+    let message_bytes = match payload.encoding.decode(&payload.message) {
+        Ok(bytes) => bytes,
+        Err(error) => return error.into_response(),
+    };
+
+    if let Err(error) = check_message_length(&message_bytes, state.config.max_message_bytes) {
+        return error.into_response();
+    }
+
+    let keypair = match parse_secret_key(&payload.secret) {
+        Ok(kp) => kp,
+        Err(error) => return error.into_response(),
+    };
+
+    let offchain_message = match OffchainMessage::new(0, &message_bytes) {
+        Ok(message) => message,
+        Err(_) => {
+            return error_response(ErrorCode::InvalidInput, "Invalid off-chain message")
+                .into_response()
+        }
+    };
+
+    let signature = match offchain_message.sign(&keypair) {
+        Ok(sig) => sig,
+        Err(_) => {
+            return error_response(ErrorCode::InternalError, "Failed to sign message")
+                .into_response()
+        }
+    };
+
+    let response_data = SignatureData {
+        signature: base64::engine::general_purpose::STANDARD.encode(signature.as_ref()),
+        public_key: keypair.pubkey().to_string(),
+        message: payload.message,
+        message_bytes_base64: None,
+        message_length: None,
+    };
+
+    let response = ApiResponse {
+        success: true,
+        data: response_data,
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+const MAX_BATCH_MESSAGES: usize = 100;
+
+async fn batch_sign_message_handler(
+    State(state): State<AppState>,
+    JsonBody(payload): JsonBody<BatchSignMessageRequest>,
+) -> impl IntoResponse {
+    if payload.secret.is_empty() || payload.messages.is_empty() {
+        return error_response(ErrorCode::MissingFields, "Missing required fields").into_response();
+    }
+
+    if payload.messages.len() > MAX_BATCH_MESSAGES {
+        return error_response(
+            ErrorCode::LimitExceeded,
+            "Too many messages, maximum is 100",
+        )
+        .into_response();
+    }
+
+    if payload.messages.iter().any(|message| message.is_empty()) {
+        return error_response(ErrorCode::MissingFields, "Messages must not be empty")
+            .into_response();
+    }
+
+    let mut decoded = Vec::with_capacity(payload.messages.len());
+    for message in &payload.messages {
+        let message_bytes = match payload.encoding.decode(message) {
+            Ok(bytes) => bytes,
+            Err(error) => return error.into_response(),
+        };
+
+        if let Err(error) = check_message_length(&message_bytes, state.config.max_message_bytes) {
+            return error.into_response();
+        }
+
+        decoded.push(message_bytes);
+    }
+
+    let keypair = match parse_secret_key(&payload.secret) {
+        Ok(kp) => kp,
+        Err(error) => return error.into_response(),
+    };
+
+    let mut signatures = Vec::with_capacity(payload.messages.len());
+    for (message, message_bytes) in payload.messages.into_iter().zip(decoded) {
+        match sign_with_keypair(&keypair, &message_bytes, message) {
+            Some(data) => signatures.push(data),
+            None => {
+                return error_response(ErrorCode::InternalError, "Failed to sign message")
+                    .into_response()
+            }
+        }
+    }
+
+    let response = ApiResponse {
+        success: true,
+        data: signatures,
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+fn parse_verify_inputs(
+    request: &VerifyMessageRequest,
+    max_message_bytes: usize,
+) -> Result<(Vec<u8>, Pubkey, Signature), ErrorResponse> {
+    let VerifyMessageRequest {
+        message,
+        signature,
+        pubkey,
+        encoding,
+    } = request;
+
+    if message.is_empty() || signature.is_empty() || pubkey.is_empty() {
+        return Err(ErrorResponse::new(
+            ErrorCode::MissingFields,
+            "Missing required fields",
+        ));
+    }
+
+    let message_bytes = encoding.decode(message)?;
+    check_message_length(&message_bytes, max_message_bytes)?;
+
+    let pubkey = pubkey.parse::<Pubkey>().map_err(|err| {
+        ErrorResponse::new(ErrorCode::InvalidPubkey, "Invalid public key").with_details(err)
+    })?;
+
+    let signature_bytes = base64::engine::general_purpose::STANDARD
+        .decode(signature)
+        .map_err(|err| {
+            ErrorResponse::new(ErrorCode::InvalidSignature, "Invalid signature format")
+                .with_details(err)
+        })?;
+
+    let signature = Signature::try_from(signature_bytes.as_slice()).map_err(|_| {
+        ErrorResponse::new(ErrorCode::InvalidSignature, "Invalid signature")
+            .with_details(format!("expected 64 bytes, got {}", signature_bytes.len()))
+    })?;
+
+    Ok((message_bytes, pubkey, signature))
+}
+
+fn verify_signature(
+    request: &VerifyMessageRequest,
+    max_message_bytes: usize,
+) -> Result<bool, ErrorResponse> {
+    let (message_bytes, pubkey, signature) = parse_verify_inputs(request, max_message_bytes)?;
+
+    Ok(signature.verify(&pubkey.to_bytes(), &message_bytes))
+}
+
+fn verify_offchain_signature(
+    request: &VerifyMessageRequest,
+    max_message_bytes: usize,
+) -> Result<bool, ErrorResponse> {
+    let (message_bytes, pubkey, signature) = parse_verify_inputs(request, max_message_bytes)?;
+
+    let offchain_message = OffchainMessage::new(0, &message_bytes)
+        .map_err(|_| ErrorResponse::new(ErrorCode::InvalidInput, "Invalid off-chain message"))?;
+
+    offchain_message
+        .verify(&pubkey, &signature)
+        .map_err(|_| ErrorResponse::new(ErrorCode::InvalidInput, "Invalid off-chain message"))
+}
+
+async fn verify_message_handler(
+    State(state): State<AppState>,
+    JsonBody(payload): JsonBody<VerifyMessageRequest>,
+) -> impl IntoResponse {
+    let is_valid = match verify_signature(&payload, state.config.max_message_bytes) {
+        Ok(valid) => valid,
+        Err(error) => return error.into_response(),
+    };
+
+    let response_data = VerifyData {
+        valid: is_valid,
+        message: payload.message,
+        pubkey: payload.pubkey,
+    };
+
+    let response = ApiResponse {
+        success: true,
+        data: response_data,
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn verify_offchain_message_handler(
+    State(state): State<AppState>,
+    JsonBody(payload): JsonBody<VerifyMessageRequest>,
+) -> impl IntoResponse {
+    let is_valid = match verify_offchain_signature(&payload, state.config.max_message_bytes) {
+        Ok(valid) => valid,
+        Err(error) => return error.into_response(),
+    };
+
+    let response_data = VerifyData {
+        valid: is_valid,
+        message: payload.message,
+        pubkey: payload.pubkey,
+    };
+
+    let response = ApiResponse {
+        success: true,
+        data: response_data,
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn batch_verify_message_handler(
+    State(state): State<AppState>,
+    JsonBody(payload): JsonBody<Vec<serde_json::Value>>,
+) -> impl IntoResponse {
+    if payload.is_empty() {
+        return error_response(ErrorCode::MissingFields, "Missing required fields").into_response();
+    }
+
+    if payload.len() > MAX_BATCH_MESSAGES {
+        return error_response(
+            ErrorCode::LimitExceeded,
+            "Too many messages, maximum is 100",
+        )
+        .into_response();
+    }
+
+    let results: Vec<BatchVerifyData> = payload
+        .into_iter()
+        .enumerate()
+        .map(|(index, value)| {
+            // Entries are parsed one at a time so a malformed entry only fails itself.
+            let item = match serde_json::from_value::<VerifyMessageRequest>(value.clone()) {
+                Ok(item) => item,
+                Err(_) => {
+                    let field = |name: &str| {
+                        value
+                            .get(name)
+                            .and_then(|v| v.as_str())
+                            .unwrap_or_default()
+                            .to_string()
+                    };
+                    return BatchVerifyData {
+                        index,
+                        valid: false,
+                        message: field("message"),
+                        pubkey: field("pubkey"),
+                        error: Some(
+                            "Malformed entry, expected message, signature and pubkey strings"
+                                .to_string(),
+                        ),
+                    };
+                }
+            };
+
+            let (valid, error) = match verify_signature(&item, state.config.max_message_bytes) {
+                Ok(valid) => (valid, None),
+                Err(error) => (false, Some(error.error)),
+            };
+
+            BatchVerifyData {
+                index,
+                valid,
+                message: item.message,
+                pubkey: item.pubkey,
+                error,
+            }
+        })
+        .collect();
+
+    let response = ApiResponse {
+        success: true,
+        data: results,
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn send_sol_handler(
+    Query(query): Query<EncodingQuery>,
+    JsonBody(payload): JsonBody<SendSolRequest>,
+) -> impl IntoResponse {
+    let lamports = match payload.lamports.parse() {
+        Ok(lamports) => lamports,
+        Err(error) => return error.into_response(),
+    };
+
+    if lamports == 0 {
+        return error_response(ErrorCode::AmountZero, "Amount must be greater than 0")
+            .into_response();
+    }
+
+    let from_pubkey = match parse_pubkey("from", &payload.from) {
+        Ok(pk) => pk,
+        Err(error) => return error.into_response(),
+    };
+
+    let to_pubkey = match parse_pubkey("to", &payload.to) {
+        Ok(pk) => pk,
+        Err(error) => return error.into_response(),
+    };
+
+    if from_pubkey == to_pubkey {
+        return error_response(
+            ErrorCode::InvalidInput,
+            "Cannot send SOL to the same address",
+        )
+        .into_response();
+    }
+
+    let mut instruction_bytes = vec![2u8, 0u8, 0u8, 0u8];
+    instruction_bytes.extend_from_slice(&lamports.to_le_bytes());
+
+    let accounts = vec![
+        AccountMeta {
+            pubkey: payload.from,
+            is_signer: true,
+            is_writable: true,
+        },
+        AccountMeta {
+            pubkey: payload.to,
+            is_signer: false,
+            is_writable: true,
+        },
+    ];
+
+    let instruction_data = InstructionData {
+        program_id: system_program::id().to_string(),
+        accounts,
+        instruction_data: query.encoding.encode(&instruction_bytes),
+    };
+
+    let response = ApiResponse {
+        success: true,
+        data: instruction_data,
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+const MAX_BULK_RECIPIENTS: usize = 100;
+
+async fn bulk_send_sol_handler(
+    Query(query): Query<EncodingQuery>,
+    JsonBody(payload): JsonBody<BulkSendSolRequest>,
+) -> impl IntoResponse {
+    let from_pubkey = match parse_pubkey("from", &payload.from) {
+        Ok(pk) => pk,
+        Err(error) => return error.into_response(),
+    };
+
+    if payload.recipients.is_empty() {
+        return error_response(
+            ErrorCode::MissingFields,
+            "At least one recipient is required",
+        )
+        .into_response();
+    }
+
+    if payload.recipients.len() > MAX_BULK_RECIPIENTS {
+        return error_response(
+            ErrorCode::LimitExceeded,
+            &format!("Too many recipients, maximum is {}", MAX_BULK_RECIPIENTS),
+        )
+        .into_response();
+    }
+
+    let mut instructions = Vec::with_capacity(payload.recipients.len());
+    let mut total_lamports: u64 = 0;
+
+    for (index, recipient) in payload.recipients.iter().enumerate() {
+        let to_pubkey = match parse_pubkey(&format!("recipients[{}].to", index), &recipient.to) {
+            Ok(pk) => pk,
+            Err(error) => return error.into_response(),
+        };
+
+        if from_pubkey == to_pubkey {
+            return error_response(
+                ErrorCode::InvalidInput,
+                &format!("Cannot send SOL to the same address (recipient {})", index),
+            )
+            .into_response();
+        }
+
+        let lamports = match recipient.lamports.parse() {
+            Ok(lamports) => lamports,
+            Err(error) => return error.into_response(),
+        };
+
+        if lamports == 0 {
+            return error_response(
+                ErrorCode::AmountZero,
+                &format!("Amount must be greater than 0 (recipient {})", index),
+            )
+            .into_response();
+        }
+
+        total_lamports = match total_lamports.checked_add(lamports) {
+            Some(total) => total,
+            None => {
+                return error_response(ErrorCode::InvalidAmount, "Total lamports overflow")
+                    .into_response()
+            }
+        };
+
+        let instruction =
+            solana_sdk::system_instruction::transfer(&from_pubkey, &to_pubkey, lamports);
+        instructions.push(instruction_to_data(instruction, query.encoding));
+    }
+
+    let response = ApiResponse {
+        success: true,
+        data: BulkTransferData {
+            instructions,
+            total_lamports,
+        },
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn send_sol_transaction_handler(
+    State(state): State<AppState>,
+    JsonBody(payload): JsonBody<SendSolTransactionRequest>,
+) -> impl IntoResponse {
+    let lamports = match payload.lamports.parse() {
+        Ok(lamports) => lamports,
+        Err(error) => return error.into_response(),
+    };
+
+    if lamports == 0 {
+        return error_response(ErrorCode::AmountZero, "Amount must be greater than 0")
+            .into_response();
+    }
+
+    let from_pubkey = match parse_pubkey("from", &payload.from) {
+        Ok(pk) => pk,
+        Err(error) => return error.into_response(),
+    };
+
+    let to_pubkey = match parse_pubkey("to", &payload.to) {
+        Ok(pk) => pk,
+        Err(error) => return error.into_response(),
+    };
+
+    if from_pubkey == to_pubkey {
+        return error_response(
+            ErrorCode::InvalidInput,
+            "Cannot send SOL to the same address",
+        )
+        .into_response();
+    }
+
+    let fee_payer = match parse_optional_pubkey("feePayer", payload.fee_payer.as_deref()) {
+        Ok(fee_payer) => fee_payer.unwrap_or(from_pubkey),
+        Err(error) => return error.into_response(),
+    };
+
+    let blockhash = match latest_blockhash(&state).await {
+        Ok(cached) => cached.blockhash,
+        Err(err) => {
+            tracing::warn!("get_latest_blockhash failed: {}", err);
+            return error_response(
+                ErrorCode::RpcError,
+                "Failed to fetch recent blockhash from RPC",
+            )
+            .into_response();
+        }
+    };
+
+    let instruction = solana_sdk::system_instruction::transfer(&from_pubkey, &to_pubkey, lamports);
+    let message = Message::new_with_blockhash(&[instruction], Some(&fee_payer), &blockhash);
+    let message_bytes = message.serialize();
+    let transaction = Transaction::new_unsigned(message);
+
+    let transaction_bytes = match bincode::serialize(&transaction) {
+        Ok(bytes) => bytes,
+        Err(_) => {
+            return error_response(ErrorCode::InternalError, "Failed to serialize transaction")
+                .into_response()
+        }
+    };
+
+    let response = ApiResponse {
+        success: true,
+        data: BuiltTransactionData {
+            transaction: base64::engine::general_purpose::STANDARD.encode(transaction_bytes),
+            message: base64::engine::general_purpose::STANDARD.encode(message_bytes),
+            blockhash: blockhash.to_string(),
+            version: "legacy".to_string(),
+        },
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn send_sol_with_seed_handler(
+    Query(query): Query<EncodingQuery>,
+    JsonBody(payload): JsonBody<SendSolWithSeedRequest>,
+) -> impl IntoResponse {
+    let lamports = match payload.lamports.parse() {
+        Ok(lamports) => lamports,
+        Err(error) => return error.into_response(),
+    };
+
+    if lamports == 0 {
+        return error_response(ErrorCode::AmountZero, "Amount must be greater than 0")
+            .into_response();
+    }
+
+    let from_pubkey = match parse_pubkey("from", &payload.from) {
+        Ok(pk) => pk,
+        Err(error) => return error.into_response(),
+    };
+
+    let from_base = match parse_pubkey("fromBase", &payload.from_base) {
+        Ok(pk) => pk,
+        Err(error) => return error.into_response(),
+    };
+
+    let from_owner = match parse_pubkey("fromOwner", &payload.from_owner) {
+        Ok(pk) => pk,
+        Err(error) => return error.into_response(),
+    };
+
+    let to_pubkey = match parse_pubkey("to", &payload.to) {
+        Ok(pk) => pk,
+        Err(error) => return error.into_response(),
+    };
+
+    if from_pubkey == to_pubkey {
+        return error_response(
+            ErrorCode::InvalidInput,
+            "Cannot send SOL to the same address",
+        )
+        .into_response();
+    }
+
+    if payload.from_seed.len() > MAX_SEED_LEN {
+        return error_response(ErrorCode::LimitExceeded, "Seed exceeds the 32 byte limit")
+            .into_response();
+    }
+
+    match Pubkey::create_with_seed(&from_base, &payload.from_seed, &from_owner) {
+        Ok(derived) if derived == from_pubkey => {}
+        _ => {
+            return error_response(
+                ErrorCode::InvalidInput,
+                "from does not match the address derived from fromBase, fromSeed and fromOwner",
+            )
+            .into_response()
+        }
+    }
+
+    let instruction = solana_sdk::system_instruction::transfer_with_seed(
+        &from_pubkey,
+        &from_base,
+        payload.from_seed,
+        &from_owner,
+        &to_pubkey,
+        lamports,
+    );
+
+    let response = ApiResponse {
+        success: true,
+        data: instruction_to_data(instruction, query.encoding),
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn create_account_handler(
+    JsonBody(payload): JsonBody<CreateAccountRequest>,
+) -> impl IntoResponse {
+    if payload.from.parse::<Pubkey>().is_err() {
+        return error_response(ErrorCode::InvalidPubkey, "Invalid sender address").into_response();
+    }
+
+    if payload.new_account.parse::<Pubkey>().is_err() {
+        return error_response(ErrorCode::InvalidPubkey, "Invalid new account address")
+            .into_response();
+    }
+
+    let owner = match payload.owner.parse::<Pubkey>() {
+        Ok(pk) => pk,
+        Err(_) => {
+            return error_response(ErrorCode::InvalidPubkey, "Invalid owner program")
+                .into_response()
+        }
+    };
+
+    let accounts = vec![
+        AccountMeta {
+            pubkey: payload.from.clone(),
+            is_signer: true,
+            is_writable: true,
+        },
+        AccountMeta {
+            pubkey: payload.new_account.clone(),
+            is_signer: true,
+            is_writable: true,
+        },
+    ];
+
+    let mut instruction_bytes = vec![0u8, 0u8, 0u8, 0u8];
+    instruction_bytes.extend_from_slice(&payload.lamports.to_le_bytes());
+    instruction_bytes.extend_from_slice(&payload.space.to_le_bytes());
+    instruction_bytes.extend_from_slice(&owner.to_bytes());
+
+    let instruction_data = InstructionData {
+        program_id: system_program::id().to_string(),
+        accounts,
+        instruction_data: base64::engine::general_purpose::STANDARD.encode(instruction_bytes),
+    };
+
+    let response = ApiResponse {
+        success: true,
+        data: instruction_data,
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn create_account_with_seed_handler(
+    Query(query): Query<EncodingQuery>,
+    JsonBody(payload): JsonBody<CreateAccountWithSeedRequest>,
+) -> impl IntoResponse {
+    let from = match parse_pubkey("from", &payload.from) {
+        Ok(pk) => pk,
+        Err(error) => return error.into_response(),
+    };
+
+    let new_account = match parse_pubkey("newAccount", &payload.new_account) {
+        Ok(pk) => pk,
+        Err(error) => return error.into_response(),
+    };
+
+    let base = match parse_pubkey("base", &payload.base) {
+        Ok(pk) => pk,
+        Err(error) => return error.into_response(),
+    };
+
+    let owner = match parse_pubkey("owner", &payload.owner) {
+        Ok(pk) => pk,
+        Err(error) => return error.into_response(),
+    };
+
+    let lamports = match payload.lamports.parse() {
+        Ok(lamports) => lamports,
+        Err(error) => return error.into_response(),
+    };
+
+    if payload.seed.len() > MAX_SEED_LEN {
+        return error_response(ErrorCode::LimitExceeded, "Seed exceeds the 32 byte limit")
+            .into_response();
+    }
+
+    match Pubkey::create_with_seed(&base, &payload.seed, &owner) {
+        Ok(derived) if derived == new_account => {}
+        _ => {
+            return error_response(
+                ErrorCode::InvalidInput,
+                "newAccount does not match the address derived from base, seed and owner",
+            )
+            .into_response()
+        }
+    }
+
+    let instruction = solana_sdk::system_instruction::create_account_with_seed(
+        &from,
+        &new_account,
+        &base,
+        &payload.seed,
+        lamports,
+        payload.space,
+        &owner,
+    );
+
+    let response = ApiResponse {
+        success: true,
+        data: SeededAccountData {
+            address: new_account.to_string(),
+            instruction: instruction_to_data(instruction, query.encoding),
+        },
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn create_nonce_handler(
+    JsonBody(payload): JsonBody<CreateNonceRequest>,
+) -> impl IntoResponse {
+    let from = match parse_pubkey("from", &payload.from) {
+        Ok(pk) => pk,
+        Err(error) => return error.into_response(),
+    };
+
+    let nonce_account = match parse_pubkey("nonceAccount", &payload.nonce_account) {
+        Ok(pk) => pk,
+        Err(error) => return error.into_response(),
+    };
+
+    let authority = match parse_pubkey("authority", &payload.authority) {
+        Ok(pk) => pk,
+        Err(error) => return error.into_response(),
+    };
+
+    let lamports = match payload.lamports.parse() {
+        Ok(lamports) => lamports,
+        Err(error) => return error.into_response(),
+    };
+
+    let minimum_balance = Rent::default().minimum_balance(solana_sdk::nonce::State::size());
+    if lamports < minimum_balance {
+        return error_response(
+            ErrorCode::InvalidAmount,
+            &format!(
+                "Lamports must be at least {} to keep the nonce account rent exempt",
+                minimum_balance
+            ),
+        )
+        .into_response();
+    }
+
+    let instructions: Vec<InstructionData> = solana_sdk::system_instruction::create_nonce_account(
+        &from,
+        &nonce_account,
+        &authority,
+        lamports,
+    )
+    .into_iter()
+    .map(|instruction| instruction_to_data(instruction, DataEncoding::Base64))
+    .collect();
+
+    let response = ApiResponse {
+        success: true,
+        data: instructions,
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn advance_nonce_handler(
+    JsonBody(payload): JsonBody<AdvanceNonceRequest>,
+) -> impl IntoResponse {
+    let nonce_account = match parse_pubkey("nonceAccount", &payload.nonce_account) {
+        Ok(pk) => pk,
+        Err(error) => return error.into_response(),
+    };
+
+    let authority = match parse_pubkey("authority", &payload.authority) {
+        Ok(pk) => pk,
+        Err(error) => return error.into_response(),
+    };
+
+    let instruction =
+        solana_sdk::system_instruction::advance_nonce_account(&nonce_account, &authority);
+
+    let response = ApiResponse {
+        success: true,
+        data: instruction_to_data(instruction, DataEncoding::Base64),
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn create_lookup_table_handler(
+    State(state): State<AppState>,
+    JsonBody(payload): JsonBody<CreateLookupTableRequest>,
+) -> impl IntoResponse {
+    let authority = match parse_pubkey("authority", &payload.authority) {
+        Ok(pk) => pk,
+        Err(error) => return error.into_response(),
+    };
+
+    let payer = match parse_pubkey("payer", &payload.payer) {
+        Ok(pk) => pk,
+        Err(error) => return error.into_response(),
+    };
+
+    // The table address is derived from a slot the runtime still has in its
+    // slot hashes, so fall back to the cluster's current slot.
+    let recent_slot = match payload.recent_slot {
+        Some(slot) => slot,
+        None => match state.rpc_client.get_slot().await {
+            Ok(slot) => slot,
+            Err(err) => {
+                tracing::warn!("get_slot failed: {}", err);
+                return error_response(
+                    ErrorCode::RpcError,
+                    "Failed to fetch current slot from RPC",
+                )
+                .into_response();
+            }
+        },
+    };
+
+    let (instruction, address) = solana_sdk::address_lookup_table::instruction::create_lookup_table(
+        authority,
+        payer,
+        recent_slot,
+    );
+
+    let response = ApiResponse {
+        success: true,
+        data: LookupTableData {
+            address: address.to_string(),
+            recent_slot,
+            instruction: instruction_to_data(instruction, DataEncoding::Base64),
+        },
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+const MAX_LOOKUP_TABLE_EXTEND_ADDRESSES: usize = 30;
+
+async fn extend_lookup_table_handler(
+    JsonBody(payload): JsonBody<ExtendLookupTableRequest>,
+) -> impl IntoResponse {
+    let lookup_table = match parse_pubkey("lookupTable", &payload.lookup_table) {
+        Ok(pk) => pk,
+        Err(error) => return error.into_response(),
+    };
+
+    let authority = match parse_pubkey("authority", &payload.authority) {
+        Ok(pk) => pk,
+        Err(error) => return error.into_response(),
+    };
+
+    let payer = match parse_pubkey("payer", &payload.payer) {
+        Ok(pk) => pk,
+        Err(error) => return error.into_response(),
+    };
+
+    if payload.addresses.is_empty() {
+        return error_response(ErrorCode::MissingFields, "At least one address is required")
+            .into_response();
+    }
+
+    if payload.addresses.len() > MAX_LOOKUP_TABLE_EXTEND_ADDRESSES {
+        return error_response(
+            ErrorCode::LimitExceeded,
+            &format!(
+                "Too many addresses, maximum is {}",
+                MAX_LOOKUP_TABLE_EXTEND_ADDRESSES
+            ),
+        )
+        .into_response();
+    }
+
+    let mut new_addresses = Vec::with_capacity(payload.addresses.len());
+    for (index, address) in payload.addresses.iter().enumerate() {
+        match parse_pubkey(&format!("addresses[{}]", index), address) {
+            Ok(pk) => new_addresses.push(pk),
+            Err(error) => return error.into_response(),
+        }
+    }
+
+    let instruction = solana_sdk::address_lookup_table::instruction::extend_lookup_table(
+        lookup_table,
+        authority,
+        Some(payer),
+        new_addresses,
+    );
+
+    let response = ApiResponse {
+        success: true,
+        data: instruction_to_data(instruction, DataEncoding::Base64),
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn allocate_handler(JsonBody(payload): JsonBody<AllocateRequest>) -> impl IntoResponse {
+    if payload.account.parse::<Pubkey>().is_err() {
+        return error_response(ErrorCode::InvalidPubkey, "Invalid account address").into_response();
+    }
+
+    let accounts = vec![AccountMeta {
+        pubkey: payload.account.clone(),
+        is_signer: true,
+        is_writable: true,
+    }];
+
+    let mut instruction_bytes = vec![8u8, 0u8, 0u8, 0u8];
+    instruction_bytes.extend_from_slice(&payload.space.to_le_bytes());
+
+    let instruction_data = InstructionData {
+        program_id: system_program::id().to_string(),
+        accounts,
+        instruction_data: base64::engine::general_purpose::STANDARD.encode(instruction_bytes),
+    };
+
+    let response = ApiResponse {
+        success: true,
+        data: instruction_data,
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn assign_handler(JsonBody(payload): JsonBody<AssignRequest>) -> impl IntoResponse {
+    if payload.account.parse::<Pubkey>().is_err() {
+        return error_response(ErrorCode::InvalidPubkey, "Invalid account address").into_response();
+    }
+
+    let owner = match payload.owner.parse::<Pubkey>() {
+        Ok(pk) => pk,
+        Err(_) => {
+            return error_response(ErrorCode::InvalidPubkey, "Invalid owner program")
+                .into_response()
+        }
+    };
+
+    let accounts = vec![AccountMeta {
+        pubkey: payload.account.clone(),
+        is_signer: true,
+        is_writable: true,
+    }];
+
+    let mut instruction_bytes = vec![1u8, 0u8, 0u8, 0u8];
+    instruction_bytes.extend_from_slice(&owner.to_bytes());
+
+    let instruction_data = InstructionData {
+        program_id: system_program::id().to_string(),
+        accounts,
+        instruction_data: base64::engine::general_purpose::STANDARD.encode(instruction_bytes),
+    };
+
+    let response = ApiResponse {
+        success: true,
+        data: instruction_data,
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn delegate_stake_handler(
+    JsonBody(payload): JsonBody<DelegateStakeRequest>,
+) -> impl IntoResponse {
+    let stake_account = match payload.stake_account.parse::<Pubkey>() {
+        Ok(pk) => pk,
+        Err(_) => {
+            return error_response(ErrorCode::InvalidPubkey, "Invalid stake account address")
+                .into_response()
+        }
+    };
+
+    let vote_account = match payload.vote_account.parse::<Pubkey>() {
+        Ok(pk) => pk,
+        Err(_) => {
+            return error_response(ErrorCode::InvalidPubkey, "Invalid vote account address")
+                .into_response()
+        }
+    };
+
+    let authority = match payload.authority.parse::<Pubkey>() {
+        Ok(pk) => pk,
+        Err(_) => {
+            return error_response(ErrorCode::InvalidPubkey, "Invalid stake authority")
+                .into_response()
+        }
+    };
+
+    let instruction =
+        solana_sdk::stake::instruction::delegate_stake(&stake_account, &authority, &vote_account);
+
+    let response = ApiResponse {
+        success: true,
+        data: instruction_to_data(instruction, DataEncoding::Base64),
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn send_token_handler(
+    Query(query): Query<EncodingQuery>,
+    JsonBody(payload): JsonBody<SendTokenRequest>,
+) -> impl IntoResponse {
+    let token_program = match resolve_token_program(payload.token_program.as_deref()) {
+        Ok(pk) => pk,
+        Err(message) => return error_response(ErrorCode::InvalidPubkey, message).into_response(),
+    };
+
+    if let Err(error) = parse_pubkey("source", &payload.source) {
+        return error.into_response();
+    }
+
+    if let Err(error) = parse_pubkey("destination", &payload.destination) {
+        return error.into_response();
+    }
+
+    if let Err(error) = parse_pubkey("mint", &payload.mint) {
+        return error.into_response();
+    }
+
+    if let Err(error) = parse_pubkey("owner", &payload.owner) {
+        return error.into_response();
+    }
+
+    let amount = match resolve_token_amount(
+        payload.amount.as_ref(),
+        payload.ui_amount.as_deref(),
+        payload.decimals,
+    ) {
+        Ok(amount) => amount,
+        Err(error) => return error.into_response(),
+    };
+
+    if amount == 0 {
+        return error_response(ErrorCode::AmountZero, "Amount must be greater than 0")
+            .into_response();
+    }
+
+    let authority_accounts = match token_authority_metas(&payload.owner, &payload.signers) {
+        Ok(accounts) => accounts,
+        Err(error) => return error.into_response(),
+    };
+
+    let mut accounts = vec![
+        AccountMeta {
+            pubkey: payload.source.clone(),
+            is_signer: false,
+            is_writable: true,
+        },
+        AccountMeta {
+            pubkey: payload.destination.clone(),
+            is_signer: false,
+            is_writable: true,
+        },
+    ];
+    accounts.extend(authority_accounts);
+
+    let mut instruction_bytes = vec![3u8];
+    instruction_bytes.extend_from_slice(&amount.to_le_bytes());
+
+    let instruction_data = InstructionData {
+        program_id: token_program.to_string(),
+        accounts: merge_duplicate_account_metas(accounts),
+        instruction_data: query.encoding.encode(&instruction_bytes),
+    };
+
+    let response = ApiResponse {
+        success: true,
+        data: instruction_data,
+    };
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn send_token_checked_handler(
+    JsonBody(payload): JsonBody<SendTokenCheckedRequest>,
+) -> impl IntoResponse {
+    let token_program = match resolve_token_program(payload.token_program.as_deref()) {
+        Ok(pk) => pk,
+        Err(message) => return error_response(ErrorCode::InvalidPubkey, message).into_response(),
+    };
+
+    if let Err(error) = parse_pubkey("source", &payload.source) {
+        return error.into_response();
+    }
+
+    if let Err(error) = parse_pubkey("destination", &payload.destination) {
+        return error.into_response();
+    }
+
+    if let Err(error) = parse_pubkey("mint", &payload.mint) {
+        return error.into_response();
+    }
+
+    if let Err(error) = parse_pubkey("owner", &payload.owner) {
+        return error.into_response();
+    }
+
+    if let Err(error) = validate_decimals(payload.decimals) {
+        return error.into_response();
+    }
+
+    if payload.amount == 0 {
+        return error_response(ErrorCode::AmountZero, "Amount must be greater than 0")
+            .into_response();
+    }
+
+    let accounts = vec![
+        AccountMeta {
+            pubkey: payload.source.clone(),
+            is_signer: false,
+            is_writable: true,
+        },
+        AccountMeta {
+            pubkey: payload.mint.clone(),
+            is_signer: false,
+            is_writable: false,
+        },
+        AccountMeta {
+            pubkey: payload.destination.clone(),
+            is_signer: false,
+            is_writable: true,
+        },
+        AccountMeta {
+            pubkey: payload.owner.clone(),
+            is_signer: true,
+            is_writable: false,
+        },
+    ];
+
+    let mut instruction_bytes = vec![12u8];
+    instruction_bytes.extend_from_slice(&payload.amount.to_le_bytes());
+    instruction_bytes.push(payload.decimals);
+
+    let instruction_data = InstructionData {
+        program_id: token_program.to_string(),
+        accounts: merge_duplicate_account_metas(accounts),
+        instruction_data: base64::engine::general_purpose::STANDARD.encode(instruction_bytes),
     };
 
     let response = ApiResponse {
@@ -215,180 +5258,989 @@ async fn mint_token_handler(Json(payload): Json<MintTokenRequest>) -> impl IntoR
         data: instruction_data,
     };
 
-    (StatusCode::OK, Json(response))
+    (StatusCode::OK, Json(response)).into_response()
 }
 
-async fn sign_message_handler(Json(payload): Json<SignMessageRequest>) -> impl IntoResponse {
-    if payload.message.is_empty() || payload.secret.is_empty() {
-        return error_response("Missing required fields").into_response();
+fn panic_response(panic: Box<dyn std::any::Any + Send + 'static>) -> Response {
+    let details = if let Some(message) = panic.downcast_ref::<String>() {
+        message.as_str()
+    } else if let Some(message) = panic.downcast_ref::<&str>() {
+        message
+    } else {
+        "unknown panic payload"
+    };
+    tracing::error!("Handler panicked: {}", details);
+
+    error_response(ErrorCode::InternalError, "Internal server error").into_response()
+}
+
+async fn metrics_middleware(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+    let started_at = Instant::now();
+
+    let response = next.run(request).await;
+
+    state
+        .metrics
+        .record(route, response.status(), started_at.elapsed());
+
+    response
+}
+
+async fn metrics_handler(State(state): State<AppState>) -> impl IntoResponse {
+    (
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        state.metrics.render(),
+    )
+}
+
+async fn pretty_print_middleware(request: Request, next: Next) -> Response {
+    let pretty = request.uri().query().is_some_and(|query| {
+        query
+            .split('&')
+            .any(|pair| matches!(pair, "pretty" | "pretty=1" | "pretty=true"))
+    });
+
+    let response = next.run(request).await;
+
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .is_some_and(|value| value.as_bytes().starts_with(b"application/json"));
+    if !pretty || !is_json {
+        return response;
     }
 
-    let secret_bytes = match bs58::decode(&payload.secret).into_vec() {
+    let (mut parts, body) = response.into_parts();
+    let bytes = match axum::body::to_bytes(body, usize::MAX).await {
         Ok(bytes) => bytes,
-        Err(_) => return error_response("Invalid secret key format").into_response(),
+        Err(_) => {
+            return error_response(ErrorCode::InternalError, "Internal server error")
+                .into_response()
+        }
     };
 
-    let keypair = match Keypair::from_bytes(&secret_bytes) {
-        Ok(kp) => kp,
-        Err(_) => return error_response("Invalid secret key").into_response(),
-    };
+    match serde_json::from_slice::<serde_json::Value>(&bytes)
+        .and_then(|value| serde_json::to_vec_pretty(&value))
+    {
+        Ok(pretty) => {
+            parts.headers.remove(header::CONTENT_LENGTH);
+            Response::from_parts(parts, axum::body::Body::from(pretty))
+        }
+        Err(_) => Response::from_parts(parts, axum::body::Body::from(bytes)),
+    }
+}
 
-    let message_bytes = payload.message.as_bytes();
-    
-    let signature = match keypair.try_sign_message(message_bytes) {
-        Ok(sig) => sig,
-        Err(_) => return error_response("Failed to sign message").into_response(),
-    };
+async fn timeout_middleware(
+    State(timeout): State<Duration>,
+    request: Request,
+    next: Next,
+) -> Response {
+    match tokio::time::timeout(timeout, next.run(request)).await {
+        Ok(response) => response,
+        Err(_) => error_response(ErrorCode::Timeout, "Request timed out").into_response(),
+    }
+}
 
-    let response_data = SignatureData {
-        signature: base64::engine::general_purpose::STANDARD.encode(signature.as_ref()),
-        public_key: keypair.pubkey().to_string(),
-        message: payload.message,
-    };
+const REQUEST_ID_HEADER: &str = "x-request-id";
+const MAX_REQUEST_ID_LEN: usize = 128;
 
-    let response = ApiResponse {
-        success: true,
-        data: response_data,
-    };
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
 
-    (StatusCode::OK, Json(response)).into_response()
+async fn request_id_middleware(mut request: Request, next: Next) -> Response {
+    let header_value = request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .filter(|value| {
+            !value.is_empty() && value.len() <= MAX_REQUEST_ID_LEN && value.to_str().is_ok()
+        })
+        .cloned()
+        .unwrap_or_else(|| {
+            HeaderValue::from_str(&uuid::Uuid::new_v4().to_string())
+                .expect("UUIDs are valid header values")
+        });
+    let request_id = header_value.to_str().unwrap_or_default().to_string();
+
+    request
+        .headers_mut()
+        .insert(REQUEST_ID_HEADER, header_value.clone());
+    let mut response = REQUEST_ID.scope(request_id, next.run(request)).await;
+    response
+        .headers_mut()
+        .insert(REQUEST_ID_HEADER, header_value);
+
+    response
+}
+
+fn make_request_span(request: &Request) -> tracing::Span {
+    let request_id = request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+
+    tracing::info_span!(
+        "request",
+        request_id = %request_id,
+        method = %request.method(),
+        path = %request.uri().path(),
+    )
 }
 
-async fn verify_message_handler(Json(payload): Json<VerifyMessageRequest>) -> impl IntoResponse {
-    if payload.message.is_empty() || payload.signature.is_empty() || payload.pubkey.is_empty() {
-        return error_response("Missing required fields").into_response();
+const PUBLIC_PATHS: [&str; 3] = ["/", "/v1/health", "/v1/health/rpc"];
+
+async fn auth_middleware(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    if state.config.api_keys.is_empty() || PUBLIC_PATHS.contains(&request.uri().path()) {
+        return next.run(request).await;
     }
 
-    let pubkey = match payload.pubkey.parse::<Pubkey>() {
-        Ok(pk) => pk,
-        Err(_) => return error_response("Invalid public key").into_response(),
-    };
+    let authorized = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|key| api_key_matches(&state.config.api_keys, key));
 
-    let signature_bytes = match base64::engine::general_purpose::STANDARD.decode(&payload.signature) {
-        Ok(bytes) => bytes,
-        Err(_) => return error_response("Invalid signature format").into_response(),
-    };
+    if !authorized {
+        return error_response(ErrorCode::Unauthorized, "Missing or invalid API key")
+            .into_response();
+    }
 
-    let signature = match solana_sdk::signature::Signature::try_from(signature_bytes.as_slice()) {
-        Ok(sig) => sig,
-        Err(_) => return error_response("Invalid signature").into_response(),
-    };
+    next.run(request).await
+}
 
-    let message_bytes = payload.message.as_bytes();
-    let is_valid = signature.verify(&pubkey.to_bytes(), message_bytes);
+// Keys are compared by SHA-256 digest so the comparison time does not depend on how
+// many leading bytes of the presented key are correct, and every configured key is
+// checked so the time does not reveal which one matched.
+fn api_key_matches(api_keys: &[String], key: &str) -> bool {
+    let presented = solana_sdk::hash::hash(key.as_bytes());
+    api_keys.iter().fold(false, |matched, allowed| {
+        matched | (solana_sdk::hash::hash(allowed.as_bytes()) == presented)
+    })
+}
 
-    let response_data = VerifyData {
-        valid: is_valid,
-        message: payload.message,
-        pubkey: payload.pubkey,
-    };
+fn resolve_api_keys() -> Vec<String> {
+    std::env::var("API_KEYS")
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|key| !key.is_empty())
+        .map(str::to_string)
+        .collect()
+}
 
-    let response = ApiResponse {
-        success: true,
-        data: response_data,
+fn resolve_cors_layer() -> Result<CorsLayer, String> {
+    let cors = CorsLayer::new()
+        .allow_methods([Method::GET, Method::POST, Method::OPTIONS])
+        .allow_headers(AllowHeaders::mirror_request())
+        .expose_headers([HeaderName::from_static(REQUEST_ID_HEADER)])
+        .max_age(Duration::from_secs(3600));
+
+    let value = match std::env::var("ALLOWED_ORIGINS") {
+        Ok(value) if !value.trim().is_empty() => value,
+        _ => return Ok(cors.allow_origin(AllowOrigin::any())),
     };
 
-    (StatusCode::OK, Json(response)).into_response()
+    let mut origins = Vec::new();
+    for origin in value
+        .split(',')
+        .map(str::trim)
+        .filter(|origin| !origin.is_empty())
+    {
+        let origin = HeaderValue::from_str(origin)
+            .map_err(|_| format!("Invalid ALLOWED_ORIGINS entry: {}", origin))?;
+        origins.push(origin);
+    }
+
+    if origins.is_empty() {
+        return Err(format!("Invalid ALLOWED_ORIGINS: {}", value));
+    }
+
+    Ok(cors.allow_origin(AllowOrigin::list(origins)))
+}
+
+#[derive(Clone)]
+enum Cluster {
+    Mainnet,
+    Devnet,
+    Testnet,
+    Custom(String),
 }
 
-async fn send_sol_handler(Json(payload): Json<SendSolRequest>) -> impl IntoResponse {
-    if payload.from.is_empty() || payload.to.is_empty() {
-        return error_response("Missing required fields").into_response();
+impl Cluster {
+    fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "mainnet" | "mainnet-beta" => Ok(Cluster::Mainnet),
+            "devnet" => Ok(Cluster::Devnet),
+            "testnet" => Ok(Cluster::Testnet),
+            url if url.starts_with("http://") || url.starts_with("https://") => {
+                Ok(Cluster::Custom(url.to_string()))
+            }
+            _ => Err(format!(
+                "Invalid cluster: {} (expected mainnet, devnet, testnet or an http(s) URL)",
+                value
+            )),
+        }
     }
 
-    if payload.lamports == 0 {
-        return error_response("Amount must be greater than 0").into_response();
+    fn name(&self) -> &str {
+        match self {
+            Cluster::Mainnet => "mainnet",
+            Cluster::Devnet => "devnet",
+            Cluster::Testnet => "testnet",
+            Cluster::Custom(_) => "custom",
+        }
     }
 
-    let from_pubkey = match payload.from.parse::<Pubkey>() {
-        Ok(pk) => pk,
-        Err(_) => return error_response("Invalid sender address").into_response(),
-    };
+    fn url(&self) -> &str {
+        match self {
+            Cluster::Mainnet => "https://api.mainnet-beta.solana.com",
+            Cluster::Devnet => "https://api.devnet.solana.com",
+            Cluster::Testnet => "https://api.testnet.solana.com",
+            Cluster::Custom(url) => url,
+        }
+    }
+}
 
-    let to_pubkey = match payload.to.parse::<Pubkey>() {
-        Ok(pk) => pk,
-        Err(_) => return error_response("Invalid recipient address").into_response(),
-    };
+fn cli_arg(long: &str, short: Option<&str>) -> Result<Option<String>, String> {
+    find_cli_arg(std::env::args().skip(1), long, short)
+}
 
-    if from_pubkey == to_pubkey {
-        return error_response("Cannot send SOL to the same address").into_response();
-    }
+fn find_cli_arg(
+    args: impl IntoIterator<Item = String>,
+    long: &str,
+    short: Option<&str>,
+) -> Result<Option<String>, String> {
+    let mut args = args.into_iter().peekable();
+    let mut value = None;
+    let prefix = format!("{}=", long);
 
-    let mut instruction_bytes = vec![2u8, 0u8, 0u8, 0u8];
-    instruction_bytes.extend_from_slice(&payload.lamports.to_le_bytes());
+    while let Some(arg) = args.next() {
+        if arg == long || short == Some(arg.as_str()) {
+            // A following flag means the value was left out, so don't swallow it.
+            value = args.next_if(|next| !next.starts_with("--"));
+        } else if let Some(inline) = arg.strip_prefix(&prefix) {
+            value = Some(inline.to_string());
+        } else {
+            continue;
+        }
 
-    let instruction_data = SolTransferData {
-        program_id: "11111111111111111111111111111112".to_string(),
-        accounts: vec![payload.from, payload.to],
-        instruction_data: base64::engine::general_purpose::STANDARD.encode(&instruction_bytes),
-    };
+        if value.as_deref().unwrap_or_default().is_empty() {
+            return Err(format!("Missing value for {}", long));
+        }
+    }
 
-    let response = ApiResponse {
-        success: true,
-        data: instruction_data,
-    };
+    Ok(value)
+}
 
-    (StatusCode::OK, Json(response)).into_response()
+fn resolve_cluster() -> Result<Cluster, String> {
+    if let Some(value) = cli_arg("--cluster", Some("-c"))?.or_else(|| std::env::var("CLUSTER").ok())
+    {
+        return Cluster::parse(&value);
+    }
+
+    match std::env::var("RPC_URL") {
+        Ok(url) => Ok(Cluster::Custom(url)),
+        Err(_) => Ok(Cluster::Devnet),
+    }
 }
 
-async fn send_token_handler(Json(payload): Json<SendTokenRequest>) -> impl IntoResponse {
-    if payload.destination.is_empty() || payload.mint.is_empty() || payload.owner.is_empty() {
-        return error_response("Missing required fields").into_response();
+const DEFAULT_MAX_BODY_BYTES: usize = 64 * 1024;
+
+fn resolve_max_body_bytes() -> Result<usize, String> {
+    match std::env::var("MAX_BODY_BYTES") {
+        Ok(value) => value
+            .parse::<usize>()
+            .map_err(|_| format!("Invalid MAX_BODY_BYTES: {}", value)),
+        Err(_) => Ok(DEFAULT_MAX_BODY_BYTES),
     }
+}
 
-    if payload.amount == 0 {
-        return error_response("Amount must be greater than 0").into_response();
+const DEFAULT_MAX_MESSAGE_BYTES: usize = 1232;
+
+fn resolve_max_message_bytes() -> Result<usize, String> {
+    match std::env::var("MAX_MESSAGE_BYTES") {
+        Ok(value) => value
+            .parse::<usize>()
+            .map_err(|_| format!("Invalid MAX_MESSAGE_BYTES: {}", value)),
+        Err(_) => Ok(DEFAULT_MAX_MESSAGE_BYTES),
     }
+}
 
-    let accounts = vec![
-        TokenAccountMeta {
-            pubkey: payload.owner.clone(),
-            is_signer: true,
-        },
-        TokenAccountMeta {
-            pubkey: payload.destination.clone(),
-            is_signer: false,
+const DEFAULT_REQUEST_TIMEOUT_SECS: u64 = 10;
+
+fn resolve_request_timeout() -> Result<Duration, String> {
+    match std::env::var("REQUEST_TIMEOUT_SECS") {
+        Ok(value) => match value.parse::<u64>() {
+            Ok(secs) if secs > 0 => Ok(Duration::from_secs(secs)),
+            _ => Err(format!("Invalid REQUEST_TIMEOUT_SECS: {}", value)),
         },
-        TokenAccountMeta {
-            pubkey: payload.mint.clone(),
-            is_signer: false,
+        Err(_) => Ok(Duration::from_secs(DEFAULT_REQUEST_TIMEOUT_SECS)),
+    }
+}
+
+const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 256;
+
+fn resolve_max_concurrent_requests() -> Result<usize, String> {
+    match std::env::var("MAX_CONCURRENT_REQUESTS") {
+        Ok(value) => match value.parse::<usize>() {
+            Ok(max) if max > 0 => Ok(max),
+            _ => Err(format!("Invalid MAX_CONCURRENT_REQUESTS: {}", value)),
         },
-    ];
+        Err(_) => Ok(DEFAULT_MAX_CONCURRENT_REQUESTS),
+    }
+}
 
-    let mut instruction_bytes = vec![3u8];
-    instruction_bytes.extend_from_slice(&payload.amount.to_le_bytes());
+async fn overloaded_handler(_: BoxError) -> impl IntoResponse {
+    error_response(
+        ErrorCode::Overloaded,
+        "Server is busy, please retry shortly",
+    )
+}
 
-    let instruction_data = TokenTransferData {
-        program_id: "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA".to_string(),
-        accounts,
-        instruction_data: base64::engine::general_purpose::STANDARD.encode(&instruction_bytes),
+fn resolve_bind_addr() -> Result<SocketAddr, String> {
+    match cli_arg("--bind", Some("-b"))?.or_else(|| std::env::var("BIND_ADDR").ok()) {
+        Some(value) => value
+            .parse::<SocketAddr>()
+            .map_err(|_| format!("Invalid bind address: {}", value)),
+        None => Ok(SocketAddr::from(([0, 0, 0, 0], 8080))),
+    }
+}
+
+fn resolve_tls_paths() -> Result<Option<(String, String)>, String> {
+    let cert = cli_arg("--tls-cert", None)?.or_else(|| std::env::var("TLS_CERT").ok());
+    let key = cli_arg("--tls-key", None)?.or_else(|| std::env::var("TLS_KEY").ok());
+
+    match (cert, key) {
+        (Some(cert), Some(key)) => Ok(Some((cert, key))),
+        (None, None) => Ok(None),
+        _ => Err("Both --tls-cert and --tls-key are required to enable TLS".to_string()),
+    }
+}
+
+impl Config {
+    fn load() -> Result<Self, String> {
+        Ok(Config {
+            cluster: resolve_cluster()?,
+            bind_addr: resolve_bind_addr()?,
+            tls_paths: resolve_tls_paths()?,
+            api_keys: resolve_api_keys(),
+            cors: resolve_cors_layer()?,
+            max_body_bytes: resolve_max_body_bytes()?,
+            max_message_bytes: resolve_max_message_bytes()?,
+            request_timeout: resolve_request_timeout()?,
+            max_concurrent_requests: resolve_max_concurrent_requests()?,
+        })
+    }
+}
+
+async fn shutdown_signal(handle: axum_server::Handle) {
+    let ctrl_c = async {
+        if let Err(err) = tokio::signal::ctrl_c().await {
+            tracing::error!("Failed to listen for Ctrl-C: {}", err);
+            std::future::pending::<()>().await;
+        }
     };
 
-    let response = ApiResponse {
-        success: true,
-        data: instruction_data,
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(err) => {
+                tracing::error!("Failed to listen for SIGTERM: {}", err);
+                std::future::pending::<()>().await;
+            }
+        }
     };
 
-    (StatusCode::OK, Json(response)).into_response()
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+
+    tracing::info!("Shutting down gracefully");
+    handle.graceful_shutdown(Some(Duration::from_secs(10)));
 }
 
-#[tokio::main]
-async fn main() {
-    let app = Router::new()
-        .route("/", get(root_handler))
+fn v1_routes(request_timeout: Duration, max_concurrent_requests: usize) -> Router<AppState> {
+    // Health probes stay outside the concurrency limit so a busy instance
+    // still reports itself healthy instead of shedding its probes.
+    let health = Router::new()
+        .route("/health", get(health_handler))
+        .route("/health/rpc", get(rpc_health_handler))
+        .route_layer(middleware::from_fn_with_state(
+            request_timeout,
+            timeout_middleware,
+        ));
+
+    health.merge(
+        api_routes(request_timeout).layer(
+            ServiceBuilder::new()
+                .layer(HandleErrorLayer::new(overloaded_handler))
+                .load_shed()
+                // Router::layer wraps every route separately, so the limit
+                // needs a shared semaphore to cap the whole API.
+                .layer(GlobalConcurrencyLimitLayer::new(max_concurrent_requests)),
+        ),
+    )
+}
+
+fn api_routes(request_timeout: Duration) -> Router<AppState> {
+    let vanity_timeout = Duration::from_millis(MAX_VANITY_TIMEOUT_MS) + Duration::from_secs(5);
+
+    Router::new()
+        .route("/version", get(version_handler))
         .route("/keypair", post(keypair_handler))
+        .route("/keypair/batch", post(batch_keypair_handler))
+        .route(
+            "/keypair/from-mnemonic",
+            post(keypair_from_mnemonic_handler),
+        )
+        .route("/keypair/from-bytes", post(keypair_from_bytes_handler))
+        .route("/keypair/vanity/ws", get(vanity_keypair_ws_handler))
+        .route("/keypair/fingerprint", get(fingerprint_handler))
         .route("/token/create", post(create_token_handler))
+        .route("/token/create/full", post(create_token_full_handler))
         .route("/token/mint", post(mint_token_handler))
+        .route("/token/mint/checked", post(mint_token_checked_handler))
+        .route("/token/close", post(close_account_handler))
+        .route("/token/burn", post(burn_token_handler))
+        .route("/token/burn/checked", post(burn_token_checked_handler))
+        .route("/token/approve", post(approve_handler))
+        .route("/token/revoke", post(revoke_handler))
+        .route("/token/sync-native", post(sync_native_handler))
+        .route("/token/multisig/create", post(create_multisig_handler))
+        .route("/token/account/parse", post(parse_token_account_handler))
+        .route("/token/mint/parse", post(parse_mint_handler))
+        .route("/token/freeze", post(freeze_account_handler))
+        .route("/token/thaw", post(thaw_account_handler))
+        .route("/token/authority", post(set_authority_handler))
+        .route("/token/amount", get(token_amount_handler))
+        .route("/token/ata", get(ata_address_handler))
+        .route("/token/ata/create", post(create_ata_handler))
+        .route("/memo", post(memo_handler))
+        .route("/convert/lamports-to-sol", get(lamports_to_sol_handler))
+        .route("/convert/sol-to-lamports", get(sol_to_lamports_handler))
+        .route("/rent", get(rent_handler))
+        .route("/pda/derive", post(pda_handler))
+        .route("/pubkey/validate", get(validate_pubkey_handler))
+        .route("/solana-pay/url", post(solana_pay_url_handler))
+        .route("/solana-pay/qr", get(solana_pay_qr_handler))
+        .route("/hash", post(hash_handler))
+        .route("/util/base58/encode", post(base58_encode_handler))
+        .route("/util/base58/decode", post(base58_decode_handler))
+        .route("/decode/instruction", post(decode_instruction_handler))
+        .route("/balance/:pubkey", get(balance_handler))
+        .route("/blockhash", get(blockhash_handler))
+        .route("/fees/priority", get(priority_fees_handler))
+        .route("/airdrop", post(airdrop_handler))
+        .route("/transaction/build", post(build_transaction_handler))
+        .route("/transaction/size", post(transaction_size_handler))
+        .route("/transaction/decode", post(decode_transaction_handler))
+        .route("/transaction/send", post(send_transaction_handler))
+        .route("/transaction/simulate", post(simulate_transaction_handler))
+        .route(
+            "/transaction/status/:signature",
+            get(signature_status_handler),
+        )
+        .route("/compute-budget", post(compute_budget_handler))
         .route("/message/sign", post(sign_message_handler))
+        .route("/message/sign/batch", post(batch_sign_message_handler))
+        .route(
+            "/message/sign/offchain",
+            post(sign_offchain_message_handler),
+        )
         .route("/message/verify", post(verify_message_handler))
+        .route("/message/verify/batch", post(batch_verify_message_handler))
+        .route(
+            "/message/verify/offchain",
+            post(verify_offchain_message_handler),
+        )
+        .route("/system/create-account", post(create_account_handler))
+        .route(
+            "/system/create-account-with-seed",
+            post(create_account_with_seed_handler),
+        )
+        .route("/system/allocate", post(allocate_handler))
+        .route("/system/assign", post(assign_handler))
+        .route("/stake/delegate", post(delegate_stake_handler))
+        .route("/nonce/create", post(create_nonce_handler))
+        .route("/nonce/advance", post(advance_nonce_handler))
+        .route("/alt/create", post(create_lookup_table_handler))
+        .route("/alt/extend", post(extend_lookup_table_handler))
         .route("/send/sol", post(send_sol_handler))
-        .route("/send/token", post(send_token_handler));
+        .route("/send/sol/with-seed", post(send_sol_with_seed_handler))
+        .route("/send/sol/transaction", post(send_sol_transaction_handler))
+        .route("/send/sol/bulk", post(bulk_send_sol_handler))
+        .route("/send/token", post(send_token_handler))
+        .route("/send/token/checked", post(send_token_checked_handler))
+        .route_layer(middleware::from_fn_with_state(
+            request_timeout,
+            timeout_middleware,
+        ))
+        .route(
+            "/keypair/vanity",
+            post(vanity_keypair_handler).layer(middleware::from_fn_with_state(
+                vanity_timeout,
+                timeout_middleware,
+            )),
+        )
+}
 
-    let addr = SocketAddr::from(([0, 0, 0, 0], 8080));
-    
-    println!("Server is running on http://{}", addr);
-    axum_server::bind(addr)
-        .serve(app.into_make_service())
-        .await
-        .unwrap();
-}
\ No newline at end of file
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")),
+        )
+        .init();
+
+    let config = Arc::new(Config::load().map_err(anyhow::Error::msg)?);
+    tracing::info!(
+        "Using {} cluster at {}",
+        config.cluster.name(),
+        config.cluster.url()
+    );
+
+    let state = AppState {
+        started_at: Instant::now(),
+        config: config.clone(),
+        metrics: Arc::new(Metrics::default()),
+        rpc_client: Arc::new(RpcClient::new(config.cluster.url().to_string())),
+        blockhash_cache: Arc::new(Mutex::new(None)),
+        vanity_searches: Arc::new(Semaphore::new(MAX_CONCURRENT_VANITY_SEARCHES)),
+    };
+
+    let app = Router::new()
+        .route("/", get(root_handler))
+        .route("/metrics", get(metrics_handler))
+        .nest(
+            "/v1",
+            v1_routes(config.request_timeout, config.max_concurrent_requests),
+        )
+        .fallback(not_found_handler)
+        .layer(CatchPanicLayer::custom(panic_response))
+        .layer(DefaultBodyLimit::max(config.max_body_bytes))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            auth_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            metrics_middleware,
+        ))
+        .layer(config.cors.clone())
+        .layer(
+            TraceLayer::new_for_http()
+                .make_span_with(make_request_span)
+                .on_response(
+                    DefaultOnResponse::new()
+                        .level(Level::INFO)
+                        .latency_unit(LatencyUnit::Millis),
+                ),
+        )
+        .layer(middleware::from_fn(request_id_middleware))
+        // Outermost so error responses from every inner layer are pretty printed too;
+        // compression has to wrap it to see the final body.
+        .layer(middleware::from_fn(pretty_print_middleware))
+        .layer(CompressionLayer::new())
+        .with_state(state);
+
+    let addr = config.bind_addr;
+
+    let tls_config =
+        match &config.tls_paths {
+            Some((cert, key)) => Some(RustlsConfig::from_pem_file(cert, key).await.with_context(
+                || format!("Failed to load TLS certificate {} and key {}", cert, key),
+            )?),
+            None => None,
+        };
+
+    let handle = axum_server::Handle::new();
+    tokio::spawn(shutdown_signal(handle.clone()));
+
+    match tls_config {
+        Some(tls_config) => {
+            println!("Server is running on https://{}", addr);
+            axum_server::bind_rustls(addr, tls_config)
+                .handle(handle)
+                .serve(app.into_make_service())
+                .await
+                .with_context(|| format!("Failed to serve on {}", addr))?;
+        }
+        None => {
+            println!("Server is running on http://{}", addr);
+            axum_server::bind(addr)
+                .handle(handle)
+                .serve(app.into_make_service())
+                .await
+                .with_context(|| format!("Failed to serve on {}", addr))?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pay_request(recipient: &str) -> SolanaPayRequest {
+        SolanaPayRequest {
+            recipient: recipient.to_string(),
+            amount: None,
+            spl_token: None,
+            reference: Vec::new(),
+            label: None,
+            message: None,
+        }
+    }
+
+    #[test]
+    fn percent_encode_keeps_unreserved_characters() {
+        assert_eq!(percent_encode("AZaz09-._~"), "AZaz09-._~");
+        assert_eq!(
+            percent_encode("Thanks for all the fish"),
+            "Thanks%20for%20all%20the%20fish"
+        );
+        assert_eq!(percent_encode("a&b=c?#/"), "a%26b%3Dc%3F%23%2F");
+        assert_eq!(percent_encode("café"), "caf%C3%A9");
+    }
+
+    #[test]
+    fn solana_pay_url_with_recipient_only() {
+        let recipient = Pubkey::new_unique().to_string();
+        let url = solana_pay_url(&pay_request(&recipient)).unwrap();
+        assert_eq!(url, format!("solana:{}", recipient));
+    }
+
+    #[test]
+    fn solana_pay_url_orders_and_encodes_parameters() {
+        let recipient = Pubkey::new_unique().to_string();
+        let mint = Pubkey::new_unique().to_string();
+        let first = Pubkey::new_unique().to_string();
+        let second = Pubkey::new_unique().to_string();
+
+        let mut request = pay_request(&recipient);
+        request.amount = Some("1.5".to_string());
+        request.spl_token = Some(mint.clone());
+        request.reference = vec![first.clone(), second.clone()];
+        request.label = Some("Michael's shop".to_string());
+        request.message = Some("Thanks for all the fish".to_string());
+
+        assert_eq!(
+            solana_pay_url(&request).unwrap(),
+            format!(
+                "solana:{}?amount=1.5&spl-token={}&reference={}&reference={}&label=Michael%27s%20shop&message=Thanks%20for%20all%20the%20fish",
+                recipient, mint, first, second
+            )
+        );
+    }
+
+    #[test]
+    fn solana_pay_url_emits_one_pair_per_reference() {
+        let recipient = Pubkey::new_unique().to_string();
+        let references: Vec<String> = (0..3).map(|_| Pubkey::new_unique().to_string()).collect();
+
+        let mut request = pay_request(&recipient);
+        request.reference = references.clone();
+
+        assert_eq!(
+            solana_pay_url(&request).unwrap(),
+            format!(
+                "solana:{}?reference={}&reference={}&reference={}",
+                recipient, references[0], references[1], references[2]
+            )
+        );
+    }
+
+    #[test]
+    fn qr_references_collects_repeated_and_comma_separated_pairs() {
+        let params = vec![
+            ("recipient".to_string(), "r".to_string()),
+            ("reference".to_string(), "a".to_string()),
+            ("label".to_string(), "shop".to_string()),
+            ("reference".to_string(), "b, c".to_string()),
+        ];
+
+        assert_eq!(qr_references(&params), vec!["a", "b", "c"]);
+        assert!(qr_references(&[]).is_empty());
+    }
+
+    #[test]
+    fn solana_pay_url_normalizes_amount() {
+        let recipient = Pubkey::new_unique().to_string();
+
+        for (input, expected) in [(".5", "0.5"), ("2.", "2"), (" 0.25 ", "0.25"), ("10", "10")] {
+            let mut request = pay_request(&recipient);
+            request.amount = Some(input.to_string());
+            assert_eq!(
+                solana_pay_url(&request).unwrap(),
+                format!("solana:{}?amount={}", recipient, expected)
+            );
+        }
+    }
+
+    #[test]
+    fn solana_pay_url_rejects_invalid_input() {
+        let recipient = Pubkey::new_unique().to_string();
+
+        let error = solana_pay_url(&pay_request("not-a-pubkey")).unwrap_err();
+        assert!(matches!(error.code, ErrorCode::InvalidPubkey));
+
+        let mut request = pay_request(&recipient);
+        request.amount = Some("0".to_string());
+        assert!(matches!(
+            solana_pay_url(&request).unwrap_err().code,
+            ErrorCode::AmountZero
+        ));
+
+        request.amount = Some("1.0000000001".to_string());
+        assert!(matches!(
+            solana_pay_url(&request).unwrap_err().code,
+            ErrorCode::InvalidAmount
+        ));
+
+        request.amount = Some("1e3".to_string());
+        assert!(matches!(
+            solana_pay_url(&request).unwrap_err().code,
+            ErrorCode::InvalidAmount
+        ));
+
+        let mut request = pay_request(&recipient);
+        request.spl_token = Some("bad-mint".to_string());
+        assert!(matches!(
+            solana_pay_url(&request).unwrap_err().code,
+            ErrorCode::InvalidPubkey
+        ));
+
+        let mut request = pay_request(&recipient);
+        request.reference = vec!["bad-reference".to_string()];
+        assert!(matches!(
+            solana_pay_url(&request).unwrap_err().code,
+            ErrorCode::InvalidPubkey
+        ));
+    }
+
+    #[test]
+    fn parse_ui_amount_scales_by_decimals() {
+        assert_eq!(parse_ui_amount("1.5", 6), Ok(1_500_000));
+        assert_eq!(parse_ui_amount(".5", 9), Ok(500_000_000));
+        assert_eq!(parse_ui_amount("5.", 2), Ok(500));
+        assert_eq!(parse_ui_amount(" 42 ", 0), Ok(42));
+        assert_eq!(parse_ui_amount("0", 9), Ok(0));
+        assert_eq!(parse_ui_amount("0.000000001", 9), Ok(1));
+    }
+
+    #[test]
+    fn parse_ui_amount_rejects_malformed_input() {
+        for input in [
+            "", ".", "+", "++1", "+-1", "1.2.3", "-1", "1e5", "abc", "1,5",
+        ] {
+            assert!(
+                parse_ui_amount(input, 9).is_err(),
+                "{:?} should be rejected",
+                input
+            );
+        }
+        assert!(parse_ui_amount("0.001", 2).is_err());
+        assert!(parse_ui_amount("18446744073709551616", 0).is_err());
+        assert!(parse_ui_amount("18446744074", 9).is_err());
+    }
+
+    #[test]
+    fn parse_sol_uses_nine_decimals() {
+        assert_eq!(parse_sol("1"), Ok(LAMPORTS_PER_SOL));
+        assert_eq!(parse_sol("0.000000001"), Ok(1));
+        assert_eq!(parse_sol("+1.5"), Ok(1_500_000_000));
+        assert!(parse_sol("0.0000000001").is_err());
+    }
+
+    #[test]
+    fn resolve_token_amount_rejects_decimals_with_raw_amount() {
+        let amount = AmountInput::Number(10);
+        assert_eq!(
+            resolve_token_amount(Some(&amount), None, None).ok(),
+            Some(10)
+        );
+        assert!(resolve_token_amount(Some(&amount), None, Some(6)).is_err());
+        assert_eq!(
+            resolve_token_amount(None, Some("1.5"), Some(6)).ok(),
+            Some(1_500_000)
+        );
+        assert!(resolve_token_amount(None, Some("1.5"), None).is_err());
+    }
+
+    fn meta(pubkey: &str, is_signer: bool, is_writable: bool) -> AccountMeta {
+        AccountMeta {
+            pubkey: pubkey.to_string(),
+            is_signer,
+            is_writable,
+        }
+    }
+
+    fn flags(accounts: &[AccountMeta]) -> Vec<(&str, bool, bool)> {
+        accounts
+            .iter()
+            .map(|account| {
+                (
+                    account.pubkey.as_str(),
+                    account.is_signer,
+                    account.is_writable,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn merge_duplicate_account_metas_unions_flags_in_place() {
+        let merged = merge_duplicate_account_metas(vec![
+            meta("a", false, true),
+            meta("b", false, false),
+            meta("a", true, false),
+        ]);
+
+        assert_eq!(
+            flags(&merged),
+            vec![("a", true, true), ("b", false, false), ("a", true, true)]
+        );
+    }
+
+    #[test]
+    fn merge_duplicate_account_metas_leaves_unique_accounts_alone() {
+        let merged =
+            merge_duplicate_account_metas(vec![meta("a", true, false), meta("b", false, true)]);
+
+        assert_eq!(flags(&merged), vec![("a", true, false), ("b", false, true)]);
+    }
+
+    #[test]
+    fn parse_secret_key_accepts_every_encoding() {
+        let keypair = Keypair::new();
+        let bytes = keypair.to_bytes();
+
+        let inputs = [
+            bs58::encode(bytes).into_string(),
+            serde_json::to_string(&bytes.to_vec()).unwrap(),
+            encode_hex(&bytes),
+            encode_hex(&bytes[..32]),
+            bs58::encode(&bytes[..32]).into_string(),
+        ];
+
+        for input in inputs {
+            let parsed = parse_secret_key(&input).unwrap();
+            assert_eq!(parsed.pubkey(), keypair.pubkey(), "{} should parse", input);
+        }
+    }
+
+    #[test]
+    fn parse_secret_key_reports_wrong_length() {
+        let error = parse_secret_key(&bs58::encode([7u8; 48]).into_string()).unwrap_err();
+        assert!(matches!(error.code, ErrorCode::InvalidSecretKey));
+        assert_eq!(error.error, "Secret key must be 64 bytes, got 48");
+    }
+
+    #[test]
+    fn parse_secret_key_rejects_mismatched_public_key() {
+        let mut bytes = Keypair::new().to_bytes();
+        bytes[32..].copy_from_slice(Keypair::new().pubkey().as_ref());
+
+        let error = parse_secret_key(&bs58::encode(bytes).into_string()).unwrap_err();
+        assert!(matches!(error.code, ErrorCode::InvalidSecretKey));
+    }
+
+    #[test]
+    fn parse_secret_key_rejects_garbage() {
+        assert!(parse_secret_key("not a key").is_err());
+        assert!(parse_secret_key("[1, 2, 3]").is_err());
+    }
+
+    #[test]
+    fn api_key_matches_any_configured_key() {
+        let keys = vec!["first-key".to_string(), "second-key".to_string()];
+
+        assert!(api_key_matches(&keys, "first-key"));
+        assert!(api_key_matches(&keys, "second-key"));
+        assert!(!api_key_matches(&keys, "second-ke"));
+        assert!(!api_key_matches(&keys, ""));
+        assert!(!api_key_matches(&[], "first-key"));
+    }
+
+    fn argv(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn find_cli_arg_reads_separate_and_inline_values() {
+        for input in [
+            argv(&["--bind", "0.0.0.0:8080"]),
+            argv(&["-b", "0.0.0.0:8080"]),
+            argv(&["--bind=0.0.0.0:8080"]),
+        ] {
+            let value = find_cli_arg(input, "--bind", Some("-b")).unwrap();
+            assert_eq!(value.as_deref(), Some("0.0.0.0:8080"));
+        }
+
+        assert_eq!(
+            find_cli_arg(argv(&["--cluster", "devnet"]), "--bind", Some("-b")),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn find_cli_arg_treats_a_following_flag_as_a_missing_value() {
+        let error = find_cli_arg(
+            argv(&["--bind", "--cluster", "devnet"]),
+            "--bind",
+            Some("-b"),
+        )
+        .unwrap_err();
+        assert_eq!(error, "Missing value for --bind");
+
+        assert_eq!(
+            find_cli_arg(
+                argv(&["--bind", "--cluster", "devnet"]),
+                "--cluster",
+                Some("-c")
+            ),
+            Ok(Some("devnet".to_string()))
+        );
+        assert!(find_cli_arg(argv(&["--bind"]), "--bind", Some("-b")).is_err());
+        assert!(find_cli_arg(argv(&["--bind="]), "--bind", Some("-b")).is_err());
+    }
+
+    fn vanity_request(prefix: &str, case_sensitive: bool) -> VanityKeypairRequest {
+        VanityKeypairRequest {
+            prefix: prefix.to_string(),
+            case_sensitive,
+            timeout_ms: None,
+        }
+    }
+
+    #[test]
+    fn vanity_prefix_is_validated_before_case_folding() {
+        assert!(VanitySearch::from_request(vanity_request("abc", true)).is_ok());
+        assert!(VanitySearch::from_request(vanity_request("l", true)).is_err());
+        assert!(VanitySearch::from_request(vanity_request("0", false)).is_err());
+
+        let search = VanitySearch::from_request(vanity_request("Lo", false)).unwrap();
+        assert_eq!(search.prefix, "lo");
+        assert!(VanitySearch::from_request(vanity_request("lO", false)).is_ok());
+    }
+}